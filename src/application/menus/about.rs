@@ -12,6 +12,7 @@ impl<T: Write> Application<T> {
                 " - https://github.com/Strophox/tetro-tui"
             ),
             vec![],
+            vec![],
         )
     }
 }
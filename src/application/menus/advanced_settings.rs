@@ -119,6 +119,28 @@ impl<T: Write> Application<T> {
                 temp_offset += 1;
             }
 
+            self.term
+                .queue(MoveTo(
+                    x_main,
+                    y_main
+                        + y_selection
+                        + 4
+                        + u16::try_from(selection_len).unwrap()
+                        + 3
+                        + temp_offset,
+                ))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        format!(
+                            "Mode files location: {}",
+                            self.temp_data.modes_dir.display()
+                        )
+                    )
+                    .italic(),
+                ))?;
+            temp_offset += 1;
+
             if let Err(e) = &self.temp_data.loadfile_result {
                 self.term
                     .queue(MoveTo(
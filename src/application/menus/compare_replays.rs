@@ -0,0 +1,406 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::MoveTo,
+    event::{
+        self, Event, KeyCode, KeyEvent,
+        KeyEventKind::{Press, Repeat},
+        KeyModifiers,
+    },
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{Clear, ClearType},
+    QueueableCommand,
+};
+use falling_tetromino_engine::{Game, InGameTime, Phase};
+
+use crate::{
+    application::{
+        menus::{replay_game::ReplayIndex, Menu, MenuUpdate},
+        Application, GameMetaData, GameRestorationData, GameSaveAnchor, Settings,
+        UncompressedInputHistory,
+    },
+    fmt_helpers::fmt_duration,
+};
+
+/// One of the replays being compared, together with its current playback state.
+struct ComparedReplay<'a> {
+    game_restoration_data: &'a GameRestorationData<UncompressedInputHistory>,
+    game_meta_data: &'a GameMetaData,
+    replay_length: InGameTime,
+    game_save_anchors: Option<Vec<GameSaveAnchor>>,
+    replay_index: ReplayIndex,
+    game: Game,
+    inputs_loaded: usize,
+    // The replay's in-game time when the shared clock is at zero.
+    time_offset: InGameTime,
+}
+
+impl ComparedReplay<'_> {
+    fn pieces_locked(&self) -> u32 {
+        self.game.state().pieces_locked.iter().sum()
+    }
+
+    fn target_time(&self, clock: InGameTime) -> InGameTime {
+        (clock + self.time_offset).min(self.replay_length)
+    }
+
+    fn seek(&mut self, clock: InGameTime) {
+        (self.game, self.inputs_loaded) = self
+            .game_restoration_data
+            .seek(self.game_save_anchors.as_deref(), self.target_time(clock));
+    }
+
+    fn advance(&mut self, clock: InGameTime) {
+        let target_time = self.target_time(clock);
+
+        self.game_restoration_data.resimulate(
+            &mut self.game,
+            &mut self.inputs_loaded,
+            self.game_restoration_data.input_history.len(),
+            target_time,
+            |_game, _input_idx, _msgs| {},
+        );
+
+        if let Some(forfeit_time) = self.game_restoration_data.forfeit {
+            if forfeit_time <= target_time {
+                let _v = self.game.forfeit();
+            }
+        }
+    }
+
+    /// Lines of text showing the board, including the piece in play.
+    fn board_lines(
+        &self,
+        settings: &Settings,
+    ) -> Vec<Vec<(String, Option<crossterm::style::Color>)>> {
+        let board = &self.game.state().board;
+        let piece_tiles = match self.game.phase() {
+            Phase::PieceInPlay { piece, .. } => piece.tiles().to_vec(),
+            _ => Vec::new(),
+        };
+
+        (0..Game::LOCK_OUT_HEIGHT)
+            .rev()
+            .map(|y| {
+                (0..Game::WIDTH)
+                    .map(|x| {
+                        if let Some(tile_id) = board[y][x] {
+                            (
+                                "██".to_owned(),
+                                settings.palette_lockedtiles().get(&tile_id).copied(),
+                            )
+                        } else if let Some((_, tile_id)) =
+                            piece_tiles.iter().find(|((px, py), _)| {
+                                usize::try_from(*px) == Ok(x) && usize::try_from(*py) == Ok(y)
+                            })
+                        {
+                            ("██".to_owned(), settings.palette().get(tile_id).copied())
+                        } else {
+                            (" .".to_owned(), None)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T: Write> Application<T> {
+    pub(in crate::application) fn run_menu_compare_replays(
+        &mut self,
+        replays: &[(
+            GameRestorationData<UncompressedInputHistory>,
+            GameMetaData,
+            InGameTime,
+        ); 2],
+    ) -> io::Result<MenuUpdate> {
+        const ANCHOR_INTERVAL: Duration = Duration::from_millis(1000);
+        const SEEK_STEP: Duration = Duration::from_secs(1);
+
+        let mut compared = Vec::with_capacity(2);
+        for (game_restoration_data, game_meta_data, replay_length) in replays {
+            self.term.queue(Clear(ClearType::All))?;
+            let (game, game_save_anchors, replay_index) = self.calculate_game_save_anchors(
+                game_restoration_data,
+                ANCHOR_INTERVAL,
+                *replay_length,
+            )?;
+            compared.push(ComparedReplay {
+                game_restoration_data,
+                game_meta_data,
+                replay_length: *replay_length,
+                game_save_anchors,
+                replay_index,
+                game,
+                inputs_loaded: 0,
+                time_offset: InGameTime::ZERO,
+            });
+        }
+
+        let refresh_time_budget =
+            Duration::from_secs_f64(self.settings.graphics().game_fps.recip());
+
+        // The shared clock both replays are played back in sync with.
+        let mut clock = InGameTime::ZERO;
+        let mut paused = false;
+        let mut aligned_at_piece: Option<usize> = None;
+        let mut piece_prompt: Option<String> = None;
+        let mut message: Option<String> = None;
+
+        let mut time_last_refresh = Instant::now();
+
+        self.term.queue(Clear(ClearType::All))?;
+
+        loop {
+            // Advance both replays.
+            let now = Instant::now();
+            if !paused {
+                clock += now.saturating_duration_since(time_last_refresh);
+            }
+            time_last_refresh = now;
+            for replay in compared.iter_mut() {
+                replay.advance(clock);
+            }
+
+            self.render_compared_replays(
+                &compared,
+                clock,
+                paused,
+                aligned_at_piece,
+                piece_prompt.as_deref(),
+                message.as_deref(),
+            )?;
+
+            // Wait for new input, or until the next frame is due.
+            let refresh_time_budget_remaining =
+                refresh_time_budget.saturating_sub(time_last_refresh.elapsed());
+            if !event::poll(refresh_time_budget_remaining)? {
+                continue;
+            }
+
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: Press | Repeat,
+                state: _,
+            }) = event::read()?
+            else {
+                continue;
+            };
+
+            // Piece prompt open: type a number and confirm with [Enter].
+            if let Some(digits) = &mut piece_prompt {
+                match code {
+                    KeyCode::Char(c @ '0'..='9') => {
+                        digits.push(c);
+                        continue;
+                    }
+
+                    KeyCode::Enter => {
+                        let n = digits.parse::<usize>().unwrap_or(0);
+                        let piece_times = compared
+                            .iter()
+                            .map(|replay| replay.replay_index.time_of_piece(n))
+                            .collect::<Option<Vec<_>>>();
+
+                        if let Some(piece_times) = piece_times {
+                            // Align both replays at the piece; the shared clock continues from the earlier one.
+                            clock = piece_times.iter().copied().min().unwrap();
+                            for (replay, piece_time) in compared.iter_mut().zip(piece_times) {
+                                replay.time_offset = piece_time - clock;
+                                replay.seek(clock);
+                            }
+                            aligned_at_piece = Some(n);
+                        } else {
+                            message = Some(format!("(Not both replays reach piece #{n})"));
+                        }
+                    }
+
+                    // Any other key cancels.
+                    _ => {}
+                }
+
+                piece_prompt = None;
+                continue;
+            }
+
+            match (code, modifiers) {
+                // [Ctrl+C]: Quit.
+                (KeyCode::Char('c' | 'C'), KeyModifiers::CONTROL) => {
+                    break Ok(MenuUpdate::Push(Menu::Quit))
+                }
+
+                // [Esc]: Stop.
+                (KeyCode::Esc | KeyCode::Char('q' | 'Q') | KeyCode::Backspace, _) => {
+                    break Ok(MenuUpdate::Pop)
+                }
+
+                // [Space]: Pause.
+                (KeyCode::Char(' '), _) => paused ^= true,
+
+                // [←|→]: Seek both replays.
+                (KeyCode::Left | KeyCode::Char('h' | 'H'), _) => {
+                    clock = clock.saturating_sub(SEEK_STEP);
+                    for replay in compared.iter_mut() {
+                        replay.seek(clock);
+                    }
+                }
+                (KeyCode::Right | KeyCode::Char('l' | 'L'), _) => {
+                    clock += SEEK_STEP;
+                }
+
+                // [0]: Restart both replays from the beginning, unaligned.
+                (KeyCode::Char('0'), _) => {
+                    clock = InGameTime::ZERO;
+                    aligned_at_piece = None;
+                    for replay in compared.iter_mut() {
+                        replay.time_offset = InGameTime::ZERO;
+                        replay.seek(clock);
+                    }
+                }
+
+                // [P]: Open prompt to seek both replays to the same piece number.
+                (KeyCode::Char('p' | 'P'), _) => piece_prompt = Some(String::new()),
+
+                // Other misc. key event: We don't care.
+                _ => {}
+            }
+
+            message = None;
+        }
+    }
+
+    fn render_compared_replays(
+        &mut self,
+        compared: &[ComparedReplay],
+        clock: InGameTime,
+        paused: bool,
+        aligned_at_piece: Option<usize>,
+        piece_prompt: Option<&str>,
+        message: Option<&str>,
+    ) -> io::Result<()> {
+        const W_BOARD: usize = 2 + 2 * Game::WIDTH;
+        let w_main = usize::from(Self::W_MAIN);
+        let w_middle = w_main - 2 * W_BOARD;
+        let (x_main, y_main) = Self::fetch_main_xy();
+
+        let [a, b] = compared else {
+            unreachable!("exactly two replays are compared")
+        };
+
+        let fmt_name = |replay: &ComparedReplay| {
+            let name = format!(
+                "{} {}",
+                replay.game_meta_data.title, replay.game_meta_data.datetime
+            );
+            format!(
+                "{:^W_BOARD$}",
+                name.chars().take(W_BOARD).collect::<String>()
+            )
+        };
+        let fmt_signed_secs = |t_0: InGameTime, t_1: InGameTime| {
+            if t_0 <= t_1 {
+                format!("+{:.2}s", (t_1 - t_0).as_secs_f64())
+            } else {
+                format!("-{:.2}s", (t_0 - t_1).as_secs_f64())
+            }
+        };
+
+        // Compare times of the latest piece reached by both.
+        let (pieces_a, pieces_b) = (a.pieces_locked(), b.pieces_locked());
+        let piece_common = usize::try_from(pieces_a.min(pieces_b)).unwrap() + 1;
+        let time_delta = match (
+            a.replay_index.time_of_piece(piece_common),
+            b.replay_index.time_of_piece(piece_common),
+        ) {
+            (Some(t_a), Some(t_b)) => fmt_signed_secs(t_a, t_b),
+            _ => "-".to_owned(),
+        };
+        let pieces_delta = i64::from(pieces_b) - i64::from(pieces_a);
+
+        let middle_lines = [
+            "Time".to_owned(),
+            fmt_duration(a.game.state().time),
+            fmt_duration(b.game.state().time),
+            String::new(),
+            "Pieces".to_owned(),
+            format!("{pieces_a} vs {pieces_b}"),
+            format!("Δ {pieces_delta:+}"),
+            String::new(),
+            "Lines".to_owned(),
+            format!(
+                "{} vs {}",
+                a.game.state().lineclears,
+                b.game.state().lineclears
+            ),
+            String::new(),
+            format!("Δtime @#{piece_common}"),
+            time_delta,
+            String::new(),
+            match aligned_at_piece {
+                Some(n) => format!("(Aligned @#{n})"),
+                None => "(Aligned @0s)".to_owned(),
+            },
+            if paused { "(Paused)" } else { "" }.to_owned(),
+            format!("({})", fmt_duration(clock)),
+        ];
+
+        let lines_a = a.board_lines(&self.settings);
+        let lines_b = b.board_lines(&self.settings);
+
+        self.term
+            .queue(MoveTo(x_main, y_main))?
+            .queue(PrintStyledContent(
+                format!("{:^w_main$}", "- Comparing Replays -").bold(),
+            ))?
+            .queue(MoveTo(x_main, y_main + 1))?
+            .queue(Print(format!(
+                "{}{:w_middle$}{}",
+                fmt_name(a),
+                "",
+                fmt_name(b)
+            )))?;
+
+        for (i, (line_a, line_b)) in lines_a.iter().zip(lines_b.iter()).enumerate() {
+            let y = y_main + 2 + u16::try_from(i).unwrap();
+            self.term.queue(MoveTo(x_main, y))?;
+            for (j, line) in [line_a, line_b].into_iter().enumerate() {
+                self.term.queue(Print("│"))?;
+                for (cell, color) in line {
+                    match color {
+                        Some(color) => self
+                            .term
+                            .queue(PrintStyledContent(cell.as_str().with(*color)))?,
+                        None => self.term.queue(Print(cell))?,
+                    };
+                }
+                self.term.queue(Print("│"))?;
+                if j == 0 {
+                    let middle = middle_lines.get(i).map_or("", String::as_str);
+                    self.term.queue(Print(format!("{middle:^w_middle$}")))?;
+                }
+            }
+        }
+
+        let bottom_line = if let Some(digits) = piece_prompt {
+            format!("Go to piece #{digits}_")
+        } else if let Some(message) = message {
+            message.to_owned()
+        } else {
+            "([Space]=pause [←|→]=seek [P]=align@piece# [0]=restart)".to_owned()
+        };
+        self.term
+            .queue(MoveTo(
+                x_main,
+                y_main + 2 + u16::try_from(Game::LOCK_OUT_HEIGHT).unwrap(),
+            ))?
+            .queue(PrintStyledContent(
+                format!("{bottom_line:^w_main$}").italic(),
+            ))?;
+
+        self.term.flush()
+    }
+}
@@ -0,0 +1,215 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor::MoveTo,
+    event::{
+        self, Event, KeyCode, KeyEvent,
+        KeyEventKind::{Press, Repeat},
+        KeyModifiers,
+    },
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{Clear, ClearType},
+    QueueableCommand,
+};
+use falling_tetromino_engine::Stat;
+
+use crate::{
+    application::{
+        menus::{Menu, MenuUpdate},
+        Application, ScoreEntry,
+    },
+    fmt_helpers::fmt_duration,
+    game_renderers::TetroTUIRenderer,
+};
+
+impl<T: Write> Application<T> {
+    pub(in crate::application) fn run_menu_daily_results(
+        &mut self,
+        cursor_pos: &mut usize,
+        camera_pos: &mut usize,
+    ) -> io::Result<MenuUpdate> {
+        const CAMERA_SIZE: usize = 11;
+        let mut export_message: Option<String> = None;
+
+        loop {
+            let w_main = Self::W_MAIN.into();
+            let (x_main, y_main) = Self::fetch_main_xy();
+            let y_selection = Self::H_MAIN / 5;
+            // Newest results are shown first.
+            let n_entries = self.scores_and_replays.daily_entries.len();
+            let entry_at = |pos: usize| n_entries - 1 - pos;
+
+            if n_entries > 0 {
+                *cursor_pos = (*cursor_pos).min(n_entries - 1);
+                *camera_pos = (*camera_pos)
+                    .min(*cursor_pos)
+                    .max((*cursor_pos + 1).saturating_sub(CAMERA_SIZE));
+            }
+
+            self.term
+                .queue(Clear(ClearType::All))?
+                .queue(MoveTo(x_main, y_main + y_selection))?
+                .queue(PrintStyledContent(
+                    format!("{:^w_main$}", "* Daily Challenge Results *").bold(),
+                ))?
+                .queue(MoveTo(x_main, y_main + y_selection + 2))?
+                .queue(Print(format!("{:^w_main$}", "──────────────────────────")))?;
+
+            if n_entries == 0 {
+                self.term
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 3))?
+                    .queue(PrintStyledContent(
+                        format!("{:^w_main$}", "No daily challenge has been played yet.").italic(),
+                    ))?
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 4))?
+                    .queue(PrintStyledContent(
+                        format!("{:^w_main$}", "Your first attempt each day counts!").italic(),
+                    ))?;
+            }
+
+            for pos in (*camera_pos..n_entries).take(CAMERA_SIZE) {
+                let (entry, opt_rep) = &self.scores_and_replays.daily_entries[entry_at(pos)];
+                let result = match entry.game_meta_data.comparison_stat.0 {
+                    Stat::TimeElapsed(_) => fmt_duration(entry.time_elapsed),
+                    Stat::PiecesLocked(_) => {
+                        format!("{} tetrominos", entry.pieces_locked.iter().sum::<u32>())
+                    }
+                    Stat::LinesCleared(_) => format!("{} lines", entry.lineclears),
+                    Stat::PointsScored(_) => format!("{} points", entry.points_scored),
+                };
+                let line = format!(
+                    "{} {}{} | {}{}",
+                    entry.game_meta_data.datetime,
+                    if entry.is_win { "" } else { "unf." },
+                    entry.game_meta_data.title,
+                    result,
+                    if opt_rep.is_some() { "°" } else { "" }
+                );
+                self.term
+                    .queue(MoveTo(
+                        x_main,
+                        y_main + y_selection + 4 + u16::try_from(pos - *camera_pos).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(if pos == *cursor_pos {
+                        format!("{:<w_main$}", format!(">{line}")).bold()
+                    } else {
+                        format!("{:<w_main$}", format!(" {line}")).reset()
+                    }))?;
+            }
+
+            self.term
+                .queue(MoveTo(
+                    x_main,
+                    y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap() + 2,
+                ))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        "(Controls: [↓|↑]=scroll [Enter]=replay° [X]=export°)"
+                    )
+                    .italic(),
+                ))?
+                .queue(MoveTo(
+                    x_main,
+                    y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap() + 3,
+                ))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        export_message.take().unwrap_or_else(|| {
+                            "(Exported replays can be checked with `--verify-replay`)".to_owned()
+                        })
+                    )
+                    .italic(),
+                ))?;
+            self.term.flush()?;
+
+            // Wait for new input.
+            match event::read()? {
+                // Quit menu.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c' | 'C'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: Press | Repeat,
+                    state: _,
+                }) => break Ok(MenuUpdate::Push(Menu::Quit)),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Esc | KeyCode::Char('q' | 'Q') | KeyCode::Backspace,
+                    kind: Press,
+                    ..
+                }) => break Ok(MenuUpdate::Pop),
+
+                // Move selector up.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up | KeyCode::Char('k' | 'K'),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_entries > 0 => {
+                    *cursor_pos = (*cursor_pos + n_entries - 1) % n_entries;
+                }
+
+                // Move selector down.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Down | KeyCode::Char('j' | 'J'),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_entries > 0 => {
+                    *cursor_pos = (*cursor_pos + 1) % n_entries;
+                }
+
+                // Watch replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Enter | KeyCode::Char('e' | 'E'),
+                    kind: Press,
+                    ..
+                }) if n_entries > 0 => {
+                    if let (
+                        ScoreEntry {
+                            game_meta_data,
+                            time_elapsed,
+                            ..
+                        },
+                        Some(game_restoration_data),
+                    ) = &self.scores_and_replays.daily_entries[entry_at(*cursor_pos)]
+                    {
+                        let game_restoration_data = game_restoration_data
+                            .clone()
+                            .map(|input_history| input_history.decompress());
+
+                        break Ok(MenuUpdate::Push(Menu::ReplayGame {
+                            game_restoration_data: Box::new(game_restoration_data),
+                            game_meta_data: game_meta_data.clone(),
+                            replay_length: *time_elapsed,
+                            game_renderer: TetroTUIRenderer::with_number(
+                                self.temp_data.renderernumber,
+                            )
+                            .into(),
+                        }));
+                    }
+                }
+
+                // Export verifiable replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('x' | 'X'),
+                    kind: Press,
+                    ..
+                }) if n_entries > 0 => {
+                    let (entry, opt_rep) =
+                        &self.scores_and_replays.daily_entries[entry_at(*cursor_pos)];
+                    export_message = Some(match opt_rep {
+                        Some(game_restoration_data) => {
+                            match Self::export_replay_verifiable(entry, game_restoration_data) {
+                                Ok(path) => format!("Exported to {}", path.display()),
+                                Err(e) => format!("Export failed: {e}"),
+                            }
+                        }
+                        None => "No replay available to export.".to_owned(),
+                    });
+                }
+
+                // Other event: don't care.
+                _ => {}
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
 
 use crossterm::{
     cursor::MoveTo,
@@ -36,6 +39,7 @@ impl<T: Write> Application<T> {
             pieces_locked,
             fall_delay_reached,
             lock_delay_reached,
+            splits,
         } = game_scoring;
         let selection = vec![
             Menu::NewGame,
@@ -66,12 +70,15 @@ impl<T: Write> Application<T> {
         let animation_delay =
             std::time::Duration::from_secs_f64(1. / self.settings.graphics().game_fps);
 
+        // Games that used undo or tools do not unlock anything.
         if *is_win
+            && game_meta_data.is_ranked()
             && game_meta_data.title == GameMode::TITLE_CLASSIC
             && !self.settings.newgame.master_mode_unlocked
         {
             self.settings.newgame.master_mode_unlocked = true;
         } else if *is_win
+            && game_meta_data.is_ranked()
             && game_meta_data.title == GameMode::TITLE_PUZZLE
             && !self.settings.newgame.experimental_mode_unlocked
         {
@@ -100,6 +107,38 @@ impl<T: Write> Application<T> {
             // event::read()?;
         }
 
+        // Splits with their labels, colored by whether they beat the best previous segment.
+        let splits_table = splits.as_ref().map(|(split_milestones, splits)| {
+            let mode_title = game_meta_data.mode_title();
+            let best_segments = self.scores_and_replays.best_segments(
+                mode_title,
+                *split_milestones,
+                Some(game_scoring),
+            );
+            let mut prev_split = Duration::ZERO;
+            let entries = splits
+                .iter()
+                .enumerate()
+                .map(|(i, split)| {
+                    let segment = split.saturating_sub(prev_split);
+                    prev_split = *split;
+                    let color = match best_segments.get(i) {
+                        Some(best_segment) if segment > *best_segment => Color::Red,
+                        _ => Color::Green,
+                    };
+                    (
+                        format!("{:>4} {}", split_milestones.label(i), fmt_duration(*split)),
+                        color,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let sum_of_best = self
+                .scores_and_replays
+                .sum_of_best(mode_title, *split_milestones);
+            (entries, sum_of_best)
+        });
+        let mut show_splits = false;
+
         let mut selected = 0usize;
         let mut refresh_fully = true;
         loop {
@@ -178,19 +217,55 @@ impl<T: Write> Application<T> {
                 ));
             }
 
-            for (i, s) in stats.iter().enumerate() {
-                self.term
-                    .queue(MoveTo(
-                        x_main,
-                        y_main + y_selection + 3 + u16::try_from(i).unwrap(),
-                    ))?
-                    .queue(Print(format!("{s:^w_main$}")))?;
-            }
+            let n_rows = match &splits_table {
+                Some((entries, sum_of_best)) if show_splits => {
+                    const SPLIT_COLUMNS: usize = 3;
+                    const W_SPLIT: usize = 20;
+                    let n_entry_rows = entries.len().div_ceil(SPLIT_COLUMNS);
+                    let x_splits =
+                        x_main + u16::try_from((w_main - SPLIT_COLUMNS * W_SPLIT) / 2).unwrap();
+                    for (i, (entry, color)) in entries.iter().enumerate() {
+                        self.term
+                            .queue(MoveTo(
+                                x_splits + u16::try_from(i / n_entry_rows * W_SPLIT).unwrap(),
+                                y_main + y_selection + 3 + u16::try_from(i % n_entry_rows).unwrap(),
+                            ))?
+                            .queue(PrintStyledContent(
+                                format!("{entry:<W_SPLIT$}").with(*color),
+                            ))?;
+                    }
+                    let sum_of_best = sum_of_best.map_or("-".to_owned(), fmt_duration);
+                    self.term
+                        .queue(MoveTo(
+                            x_main,
+                            y_main + y_selection + 3 + u16::try_from(n_entry_rows).unwrap(),
+                        ))?
+                        .queue(Print(format!(
+                            "{:^w_main$}",
+                            format!("Sum of best: {sum_of_best} (stats: [S])")
+                        )))?;
+                    n_entry_rows + 1
+                }
+                _ => {
+                    if splits_table.is_some() {
+                        stats.push("(splits: [S])".to_owned());
+                    }
+                    for (i, s) in stats.iter().enumerate() {
+                        self.term
+                            .queue(MoveTo(
+                                x_main,
+                                y_main + y_selection + 3 + u16::try_from(i).unwrap(),
+                            ))?
+                            .queue(Print(format!("{s:^w_main$}")))?;
+                    }
+                    stats.len()
+                }
+            };
 
             self.term
                 .queue(MoveTo(
                     x_main,
-                    y_main + y_selection + 3 + u16::try_from(stats.len()).unwrap(),
+                    y_main + y_selection + 3 + u16::try_from(n_rows).unwrap(),
                 ))?
                 .queue(Print(format!("{:^w_main$}", "──────────────────────────")))?;
 
@@ -203,7 +278,7 @@ impl<T: Write> Application<T> {
                 self.term
                     .queue(MoveTo(
                         x_main,
-                        y_main + y_selection + 3 + u16::try_from(stats.len() + 2 + i).unwrap(),
+                        y_main + y_selection + 3 + u16::try_from(n_rows + 2 + i).unwrap(),
                     ))?
                     .queue(Print(format!(
                         "{:^w_main$}",
@@ -265,6 +340,15 @@ impl<T: Write> Application<T> {
                     selected += 1;
                 }
 
+                // Toggle splits table.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('s' | 'S'),
+                    kind: Press,
+                    ..
+                }) if splits_table.is_some() => {
+                    show_splits = !show_splits;
+                }
+
                 Event::Resize(..) => {}
 
                 // Other event: don't care.
@@ -3,14 +3,18 @@ pub mod adjust_gameplay;
 pub mod adjust_graphics;
 pub mod adjust_keybinds;
 pub mod advanced_settings;
+pub mod compare_replays;
+pub mod daily_results;
 pub mod game_ended;
 pub mod new_game;
 pub mod pause;
 pub mod play_game;
 pub mod replay_game;
+pub mod savepoints;
 pub mod scores_and_replays;
 pub mod settings;
 pub mod statistics;
+pub mod tas_editor;
 pub mod title;
 
 use std::io::{self, Write};
@@ -23,14 +27,15 @@ use crossterm::{
         KeyModifiers,
     },
     style::{Print, PrintStyledContent, Stylize},
-    terminal::{Clear, ClearType},
+    terminal::{self, Clear, ClearType},
     QueueableCommand,
 };
 use falling_tetromino_engine::{Game, InGameTime};
 
 use crate::{
     application::{
-        Application, GameMetaData, GameRestorationData, ScoreEntry, UncompressedInputHistory,
+        Application, GameMetaData, GameRestorationData, ScoreEntry, SplitRecorder,
+        UncompressedInputHistory,
     },
     game_renderers::TetroTUIRenderer,
 };
@@ -51,8 +56,13 @@ pub enum Menu {
         game_meta_data: GameMetaData,
         // game_statistics: Statistics,
         game_renderer: Box<TetroTUIRenderer>,
+        /// Splits taken so far, kept across pauses; Set up when the game is first entered.
+        split_recorder: Option<Box<SplitRecorder>>,
+    },
+    Pause {
+        /// The paused game's board, to be shared as fumen.
+        board_fumen: String,
     },
-    Pause,
     Settings,
     AdjustGraphics,
     AdjustKeybinds,
@@ -76,6 +86,26 @@ pub enum Menu {
         replay_length: InGameTime,
         game_renderer: Box<TetroTUIRenderer>,
     },
+    TasEditor {
+        game_restoration_data: Box<GameRestorationData<UncompressedInputHistory>>,
+        game_meta_data: GameMetaData,
+        cursor_pos: usize,
+        unsaved_edits: bool,
+    },
+    Savepoints,
+    DailyResults {
+        cursor_pos: usize,
+        camera_pos: usize,
+    },
+    CompareReplays {
+        replays: Box<
+            [(
+                GameRestorationData<UncompressedInputHistory>,
+                GameMetaData,
+                InGameTime,
+            ); 2],
+        >,
+    },
     Statistics,
     About,
     Quit,
@@ -89,7 +119,7 @@ impl std::fmt::Display for Menu {
             Menu::PlayGame { game_meta_data, .. } => {
                 &format!("Playing Game ({})", game_meta_data.title)
             }
-            Menu::Pause => "Pause",
+            Menu::Pause { .. } => "Pause",
             Menu::Settings => "Settings",
             Menu::AdjustGraphics => "Adjust Graphics",
             Menu::AdjustKeybinds => "Adjust Keybinds",
@@ -101,6 +131,12 @@ impl std::fmt::Display for Menu {
             Menu::ReplayGame { game_meta_data, .. } => {
                 &format!("Replaying Game ({})", game_meta_data.title)
             }
+            Menu::TasEditor { game_meta_data, .. } => {
+                &format!("Editing Inputs ({})", game_meta_data.title)
+            }
+            Menu::Savepoints => "Savepoints",
+            Menu::DailyResults { .. } => "Daily Challenge Results",
+            Menu::CompareReplays { .. } => "Comparing Replays",
             Menu::Statistics => "Statistics",
             Menu::About => "About",
             Menu::Quit => "Quit",
@@ -114,6 +150,7 @@ impl<T: Write> Application<T> {
         &mut self,
         current_menu_name: &str,
         selection: Vec<Menu>,
+        notes: Vec<String>,
     ) -> io::Result<MenuUpdate> {
         let mut easteregg = 0isize;
         let mut selected = 0usize;
@@ -182,6 +219,24 @@ impl<T: Write> Application<T> {
                         .italic(),
                     ))?;
             }
+            let mut y_note = y_main + y_selection + 4 + u16::try_from(n_names + 4).unwrap();
+            for note in &notes {
+                if note.chars().count() <= w_main {
+                    self.term
+                        .queue(MoveTo(x_main, y_note))?
+                        .queue(Print(format!("{note:^w_main$}")))?;
+                    y_note += 1;
+                } else {
+                    // Notes too wide for the menu (e.g. long fumens) are wrapped at the edge of the terminal, so they can still be copied whole.
+                    let w_console = usize::from(terminal::size()?.0).max(1);
+                    for line in note.chars().collect::<Vec<_>>().chunks(w_console) {
+                        self.term
+                            .queue(MoveTo(0, y_note))?
+                            .queue(Print(line.iter().collect::<String>()))?;
+                        y_note += 1;
+                    }
+                }
+            }
             if easteregg.abs() == 42 {
                 self.term
                     .queue(Clear(ClearType::All))?
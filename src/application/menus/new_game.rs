@@ -1,6 +1,7 @@
 use std::{
     io::{self, Write},
     num::NonZeroU32,
+    ops::{Add, Sub},
     time::Duration,
 };
 
@@ -16,41 +17,149 @@ use crossterm::{
     QueueableCommand,
 };
 use falling_tetromino_engine::{
-    DelayParameters, ExtDuration, ExtNonNegF64, Game, GameLimits, InGameTime, Stat,
+    DelayParameters, ExtDuration, ExtNonNegF64, Game, GameBuilder, GameLimits, InGameTime, Stat,
 };
 
 use crate::{
     application::{
         menus::{Menu, MenuUpdate},
         Application, GameMetaData, GameRestorationData, GameSave, GameplaySettings, Glyphset,
-        NewGameSettings, UncompressedInputHistory,
+        NewGameSettings, Statistics, UncompressedInputHistory,
     },
     fmt_helpers::{fmt_button_input, fmt_duration, fmt_hertz, FmtBool},
     game_modes::{
-        game_modifiers::{self, Combo},
-        GameMode,
+        game_modifiers::{Combo, PieceSequence, Puzzle},
+        load_mode_files, GameMode,
     },
     game_renderers::{Renderer, TetroTUIRenderer},
 };
 
+/// Text typed in for the custom game.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum CustomTextInput {
+    /// A fumen to import as start board or puzzle.
+    Fumen,
+    /// A fixed piece sequence to deal.
+    Pieces,
+}
+
+/// Names of the limits of a custom game, in the order they are selected in the menu.
+const CUSTOM_LIMIT_NAMES: [&str; 4] = ["Time", "Points", "Pieces", "Lines"];
+
+/// Enable a limit with its default, or increase/decrease it, disabling it once decreased to zero.
+fn step_limit<V>(limit: &mut Option<(V, bool)>, default: V, step: V, increase: bool)
+where
+    V: Copy + Ord + Add<Output = V> + Sub<Output = V>,
+{
+    *limit = match (*limit, increase) {
+        (None, true) => Some((default, true)),
+        (None, false) => None,
+        (Some((value, to_win)), true) => Some((value + step, to_win)),
+        (Some((value, to_win)), false) => (value > step).then(|| (value - step, to_win)),
+    };
+}
+
+/// Step the custom game limit at the given index of [`CUSTOM_LIMIT_NAMES`].
+fn step_custom_limit(limits: &mut GameLimits, limit_idx: usize, increase: bool) {
+    match limit_idx {
+        0 => step_limit(
+            &mut limits.time_elapsed,
+            Duration::from_secs(300),
+            Duration::from_secs(10),
+            increase,
+        ),
+        1 => step_limit(&mut limits.points_scored, 200, 10, increase),
+        2 => step_limit(&mut limits.pieces_locked, 100, 1, increase),
+        3 => step_limit(&mut limits.lines_cleared, 40, 1, increase),
+        _ => unreachable!(),
+    }
+}
+
+/// The custom game limit at the given index of [`CUSTOM_LIMIT_NAMES`], as formatted value and whether it is to win.
+fn custom_limit(limits: &GameLimits, limit_idx: usize) -> Option<(String, bool)> {
+    match limit_idx {
+        0 => limits
+            .time_elapsed
+            .map(|(t, to_win)| (fmt_duration(t), to_win)),
+        1 => limits
+            .points_scored
+            .map(|(s, to_win)| (s.to_string(), to_win)),
+        2 => limits
+            .pieces_locked
+            .map(|(p, to_win)| (p.to_string(), to_win)),
+        3 => limits
+            .lines_cleared
+            .map(|(l, to_win)| (l.to_string(), to_win)),
+        _ => unreachable!(),
+    }
+}
+
+/// Whether the game is played in puzzle stages, be they built-in or imported.
+fn is_puzzle(game: &Game) -> bool {
+    game.modifiers
+        .iter()
+        .any(|modifier| modifier.id() == Puzzle::MOD_ID)
+}
+
+/// Check that a stored custom game may be given the name, which then becomes its title in the scoreboard.
+///
+/// It must not pass for a built-in mode (e.g. to unlock modes or rank among its scores) nor for another stored custom game.
+fn check_preset_name(name: &str, other_preset_names: &[&str]) -> Result<(), String> {
+    const BUILT_IN_TITLES: &[&str] = &[
+        GameMode::TITLE_SWIFT,
+        GameMode::TITLE_CLASSIC,
+        GameMode::TITLE_MASTER,
+        GameMode::TITLE_PUZZLE,
+        GameMode::TITLE_SANDBOX,
+        GameMode::TITLE_CHEESE,
+        GameMode::TITLE_COMBO,
+        GameMode::TITLE_DAILY,
+        GameMode::TITLE_ASCENT,
+    ];
+
+    // Resumed games are marked with trailing `'`.
+    let title = name.trim_end_matches('\'');
+    if title.is_empty() {
+        Err("empty name".to_owned())
+    } else if BUILT_IN_TITLES.contains(&title) {
+        Err("name of built-in mode".to_owned())
+    } else if let Some(prefix) = Statistics::BLACKLIST_TITLE_PREFIXES
+        .iter()
+        .chain(&[GameMode::TITLE_DAILY])
+        .find(|prefix| title.starts_with(*prefix))
+    {
+        Err(format!("starts with {prefix:?}"))
+    } else if other_preset_names.contains(&name) {
+        Err("name already taken".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
 impl<T: Write> Application<T> {
     pub(in crate::application) fn run_menu_new_game(&mut self) -> io::Result<MenuUpdate> {
         let mut selected = 0usize;
         let mut customization_selected = 0usize;
+        // Which of the custom game limits is being edited.
+        let mut limit_selected = 0usize;
 
         let lowerbound_cheese = NonZeroU32::new(10).unwrap();
         let lowerbound_combo = NonZeroU32::new(10).unwrap();
 
-        let d_time = Duration::from_secs(10);
-        let d_score = 10;
-        let d_pieces = 1;
-        let d_lines = 1;
-
         let d_fall_delay = Duration::from_millis(100).into();
         let mult_fall_delay = ExtNonNegF64::from(10);
         let lowerbound_fall_delay: ExtDuration = Duration::from_millis(1).into();
         let upperbound_fall_delay: ExtDuration = Duration::from_secs(1000).into();
 
+        // Name being typed when renaming the selected custom preset, and why the last attempt to enter it failed.
+        let mut renaming: Option<(String, Option<String>)> = None;
+
+        // Text being typed (or pasted) for the custom game, and the error of the last attempt to enter it.
+        let mut custom_text_input: Option<(CustomTextInput, String, Option<String>)> = None;
+
+        // Modes defined in mode files, loaded once when entering the menu.
+        let mode_files = load_mode_files(&self.temp_data.modes_dir);
+
         loop {
             #[allow(clippy::type_complexity)]
             let mut game_modes = vec![
@@ -66,6 +175,7 @@ impl<T: Write> Application<T> {
                     self.settings.newgame.combo_initial_layout,
                     self.settings.newgame.combo_limit,
                 ),
+                GameMode::sandbox(),
             ];
 
             if self.settings.newgame.master_mode_unlocked {
@@ -76,6 +186,42 @@ impl<T: Write> Application<T> {
                 game_modes.push(GameMode::ascent())
             }
 
+            // Modes from mode files come after the built-in modes, those which failed to load show their error.
+            let mut broken_mode_file_idxs = Vec::new();
+            for mode_file in &mode_files {
+                match mode_file {
+                    Ok(mode_file) => game_modes.push(mode_file.clone().into_game_mode()),
+                    Err((file_name, e)) => {
+                        broken_mode_file_idxs.push(game_modes.len());
+                        game_modes.push(GameMode {
+                            title: format!("{file_name} (!)"),
+                            description: format!("ERROR: {e}"),
+                            stat_and_order_desc: (Stat::PointsScored(0), false),
+                            build: Box::new(|builder: &GameBuilder| builder.build()),
+                        });
+                    }
+                }
+            }
+
+            // Stored custom games come after those.
+            let first_preset_idx = game_modes.len();
+            for (name, preset) in &self.settings.custom_game_slotmachine.slots {
+                game_modes.push(preset.clone().into_game_mode(name.clone()));
+            }
+            let preset_idxs = first_preset_idx..game_modes.len();
+
+            // The daily challenge comes last.
+            let today = chrono::Utc::now().date_naive();
+            let daily_attempted = self.scores_and_replays.daily_last_attempted.as_deref()
+                == Some(today.to_string().as_str());
+            let daily_description = if daily_attempted {
+                "Official try done [S]=results"
+            } else {
+                "1 official try today [S]=results"
+            };
+            game_modes.push(GameMode::daily(today, daily_description.to_owned()));
+            let daily_idx = game_modes.len() - 1;
+
             // First part: rendering the menu.
             let w_main = Self::W_MAIN.into();
             let (x_main, y_main) = Self::fetch_main_xy();
@@ -115,7 +261,13 @@ impl<T: Write> Application<T> {
                     .queue(Print(format!(
                         "{:^w_main$}",
                         if i == selected {
-                            format!(">> {title}: {description} <<")
+                            match &renaming {
+                                Some((new_name, None)) => format!(">> {new_name}_ <<"),
+                                Some((new_name, Some(e))) => {
+                                    format!(">> (ERROR: {e}) {new_name}_ <<")
+                                }
+                                None => format!(">> {title}: {description} <<"),
+                            }
                         } else {
                             title.to_string()
                         }
@@ -123,12 +275,13 @@ impl<T: Write> Application<T> {
             }
             // Render load savepoint option.
             if let Some(GameSave {
-                game_meta_data,
+                name,
                 game_restoration_data: GameRestorationData { input_history, .. },
                 inputs_to_load,
+                ..
             }) = &self.game_saves.1.get(self.game_saves.0)
             {
-                let load_title = &game_meta_data.title;
+                let load_title = name;
                 let load_offset_max = input_history.len();
                 self.term
                     .queue(MoveTo(
@@ -162,11 +315,19 @@ impl<T: Write> Application<T> {
                 ))?
                 .queue(Print(format!(
                     "{:^w_main$}",
-                    if selected == selection_len - 1 {
+                    if let (true, Some((input_kind, text, error))) =
+                        (selected == selection_len - 1, &custom_text_input)
+                    {
+                        let error = match error {
+                            Some(e) => format!(" (ERROR: {e})"),
+                            None => String::new(),
+                        };
+                        format!(">> {input_kind:?}{error}: {text}_ <<")
+                    } else if selected == selection_len - 1 {
                         format!(
-                            "{:<42}",
+                            "{:<50}",
                             format!(
-                                "{} Custom: [Del]=reset{}{}",
+                                "{} Custom: [Del]=reset [S]=store [F]=fumen [P]=pieces{}{}{}",
                                 if customization_selected == 0 {
                                     ">>"
                                 } else {
@@ -177,8 +338,20 @@ impl<T: Write> Application<T> {
                                 } else {
                                     ""
                                 },
+                                if self.settings.newgame.custom_piece_sequence.is_some() {
+                                    " *queue"
+                                } else {
+                                    ""
+                                },
                                 if self.settings.newgame.custom_encoded_board.is_some() {
                                     " *board"
+                                } else if self
+                                    .settings
+                                    .newgame
+                                    .custom_encoded_puzzle_stages
+                                    .is_some()
+                                {
+                                    " *puzzle"
                                 } else {
                                     ""
                                 },
@@ -212,9 +385,17 @@ impl<T: Write> Application<T> {
                         (!self.settings.newgame.custom_fall_delay_params.is_constant())
                             .fmt_on_off()
                     ),
+                    match custom_limit(&self.settings.newgame.custom_limits, limit_selected) {
+                        Some((value, to_win)) => format!(
+                            "| {} limit = {value} to {} [W] [→]",
+                            CUSTOM_LIMIT_NAMES[limit_selected],
+                            if to_win { "win" } else { "lose" }
+                        ),
+                        None => format!("| {} limit = off [→]", CUSTOM_LIMIT_NAMES[limit_selected]),
+                    },
                     format!(
-                        "| Limit = {:?} [→]",
-                        self.settings.newgame.custom_win_condition
+                        "| Limits: {}",
+                        self.settings.newgame.custom_game_preset().limit_title()
                     ),
                 ];
                 for (j, stat_str) in stats_strs.into_iter().enumerate() {
@@ -228,16 +409,7 @@ impl<T: Write> Application<T> {
                                     .unwrap(),
                         ))?
                         .queue(Print(if j + 1 == customization_selected {
-                            format!(
-                                ">{stat_str}{}",
-                                if customization_selected != 3
-                                    || self.settings.newgame.custom_win_condition.is_some()
-                                {
-                                    " [↓|↑]"
-                                } else {
-                                    ""
-                                }
-                            )
+                            format!(">{stat_str} [↓|↑]")
                         } else {
                             stat_str
                         }))?;
@@ -245,8 +417,90 @@ impl<T: Write> Application<T> {
             }
             self.term.flush()?;
             // Wait for new input.
+            let event = event::read()?;
+
+            // Renaming consumes all key presses.
+            if let Some((new_name, error)) = &mut renaming {
+                if let Event::Key(KeyEvent {
+                    code,
+                    kind: Press | Repeat,
+                    ..
+                }) = event
+                {
+                    match code {
+                        KeyCode::Enter => {
+                            let slots = &self.settings.custom_game_slotmachine.slots;
+                            let other_preset_names = slots
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| *i != selected - first_preset_idx)
+                                .map(|(_, (name, _))| name.as_str())
+                                .collect::<Vec<_>>();
+                            match check_preset_name(new_name, &other_preset_names) {
+                                Ok(()) => {
+                                    self.settings.custom_game_slotmachine.slots
+                                        [selected - first_preset_idx]
+                                        .0 = std::mem::take(new_name);
+                                    renaming = None;
+                                }
+                                Err(e) => *error = Some(e),
+                            }
+                        }
+                        KeyCode::Esc => renaming = None,
+                        KeyCode::Backspace => {
+                            new_name.pop();
+                        }
+                        KeyCode::Char(c) => new_name.push(c),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+
+            // Typing text for the custom game consumes all key presses.
+            if let Some((input_kind, text, error)) = &mut custom_text_input {
+                if let Event::Key(KeyEvent {
+                    code,
+                    kind: Press | Repeat,
+                    ..
+                }) = event
+                {
+                    match code {
+                        KeyCode::Enter => {
+                            let result = match input_kind {
+                                CustomTextInput::Fumen => {
+                                    NewGameSettings::decode_fumen(text).map(|fumen_import| {
+                                        self.settings.newgame.load_fumen(fumen_import);
+                                    })
+                                }
+                                // An empty sequence turns it off.
+                                CustomTextInput::Pieces if text.trim().is_empty() => {
+                                    self.settings.newgame.custom_piece_sequence = None;
+                                    Ok(())
+                                }
+                                CustomTextInput::Pieces => PieceSequence::parse(text).map(|_| {
+                                    self.settings.newgame.custom_piece_sequence =
+                                        Some(text.clone());
+                                }),
+                            };
+                            match result {
+                                Ok(()) => custom_text_input = None,
+                                Err(e) => *error = Some(e),
+                            }
+                        }
+                        KeyCode::Esc => custom_text_input = None,
+                        KeyCode::Backspace => {
+                            text.pop();
+                        }
+                        KeyCode::Char(c) => text.push(c),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+
             let mut immediately_start_new_game = false;
-            match event::read()? {
+            match event {
                 // Quit app.
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('c' | 'C'),
@@ -350,21 +604,11 @@ impl<T: Write> Application<T> {
                                     .unwrap();
                             }
                             3 => {
-                                match self.settings.newgame.custom_win_condition {
-                                    Some(Stat::TimeElapsed(ref mut t)) => {
-                                        *t += d_time;
-                                    }
-                                    Some(Stat::PiecesLocked(ref mut p)) => {
-                                        *p += d_pieces;
-                                    }
-                                    Some(Stat::LinesCleared(ref mut l)) => {
-                                        *l += d_lines;
-                                    }
-                                    Some(Stat::PointsScored(ref mut s)) => {
-                                        *s += d_score;
-                                    }
-                                    None => {}
-                                };
+                                step_custom_limit(
+                                    &mut self.settings.newgame.custom_limits,
+                                    limit_selected,
+                                    true,
+                                );
                             }
                             _ => unreachable!(),
                         }
@@ -434,21 +678,11 @@ impl<T: Write> Application<T> {
                                     .unwrap();
                             }
                             3 => {
-                                match self.settings.newgame.custom_win_condition {
-                                    Some(Stat::TimeElapsed(ref mut t)) => {
-                                        *t = t.saturating_sub(d_time);
-                                    }
-                                    Some(Stat::PiecesLocked(ref mut p)) => {
-                                        *p = p.saturating_sub(d_pieces);
-                                    }
-                                    Some(Stat::LinesCleared(ref mut l)) => {
-                                        *l = l.saturating_sub(d_lines);
-                                    }
-                                    Some(Stat::PointsScored(ref mut s)) => {
-                                        *s = s.saturating_sub(d_score);
-                                    }
-                                    None => {}
-                                };
+                                step_custom_limit(
+                                    &mut self.settings.newgame.custom_limits,
+                                    limit_selected,
+                                    false,
+                                );
                             }
                             _ => unreachable!(),
                         }
@@ -530,16 +764,9 @@ impl<T: Write> Application<T> {
                 }) => {
                     // If custom gamemode selected, allow incrementing stat selection.
                     if selected == selection_len - 1 {
-                        // If reached last stat, cycle through stats for limit.
+                        // If reached last stat, cycle through which limit to edit.
                         if customization_selected == customization_selection_size - 1 {
-                            self.settings.newgame.custom_win_condition =
-                                match self.settings.newgame.custom_win_condition {
-                                    Some(Stat::TimeElapsed(_)) => Some(Stat::PointsScored(200)),
-                                    Some(Stat::PointsScored(_)) => Some(Stat::PiecesLocked(100)),
-                                    Some(Stat::PiecesLocked(_)) => Some(Stat::LinesCleared(40)),
-                                    Some(Stat::LinesCleared(_)) => None,
-                                    None => Some(Stat::TimeElapsed(Duration::from_secs(300))),
-                                };
+                            limit_selected = (limit_selected + 1) % CUSTOM_LIMIT_NAMES.len();
                         } else {
                             customization_selected += 1
                         }
@@ -603,12 +830,19 @@ impl<T: Write> Application<T> {
                     modifiers,
                     ..
                 }) => {
-                    if selected == selection_len - 1 {
+                    if preset_idxs.contains(&selected) {
+                        self.settings
+                            .custom_game_slotmachine
+                            .slots
+                            .remove(selected - first_preset_idx);
+                    } else if selected == selection_len - 1 {
                         self.settings.newgame.custom_seed = None;
                         self.settings.newgame.custom_encoded_board = None;
+                        self.settings.newgame.custom_encoded_puzzle_stages = None;
+                        self.settings.newgame.custom_piece_sequence = None;
                         self.settings.newgame.custom_fall_delay_params =
                             DelayParameters::standard_fall();
-                        self.settings.newgame.custom_win_condition = None;
+                        self.settings.newgame.custom_limits = GameLimits::new();
                     } else if selected < game_modes.len()
                         && game_modes[selected]
                             .title
@@ -645,6 +879,88 @@ impl<T: Write> Application<T> {
                     }
                 }
 
+                // Store custom game as named mode.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('s' | 'S'),
+                    kind: Press,
+                    ..
+                }) if selected == selection_len - 1 => {
+                    let preset = self.settings.newgame.custom_game_preset();
+                    let slot_idx = self.settings.custom_game_slotmachine.push_slot(preset);
+                    // Select the newly listed mode.
+                    selected = first_preset_idx + slot_idx;
+                    customization_selected = 0;
+                }
+
+                // Toggle whether selected custom limit is to win or lose.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('w' | 'W'),
+                    kind: Press,
+                    ..
+                }) if selected == selection_len - 1 && customization_selected == 3 => {
+                    let limits = &mut self.settings.newgame.custom_limits;
+                    let to_win = match limit_selected {
+                        0 => limits.time_elapsed.as_mut().map(|(_, to_win)| to_win),
+                        1 => limits.points_scored.as_mut().map(|(_, to_win)| to_win),
+                        2 => limits.pieces_locked.as_mut().map(|(_, to_win)| to_win),
+                        3 => limits.lines_cleared.as_mut().map(|(_, to_win)| to_win),
+                        _ => unreachable!(),
+                    };
+                    if let Some(to_win) = to_win {
+                        *to_win = !*to_win;
+                    }
+                }
+
+                // Import fumen into custom game.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('f' | 'F'),
+                    kind: Press,
+                    ..
+                }) if selected == selection_len - 1 => {
+                    custom_text_input = Some((CustomTextInput::Fumen, String::new(), None));
+                }
+
+                // Enter fixed piece sequence for custom game.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('p' | 'P'),
+                    kind: Press,
+                    ..
+                }) if selected == selection_len - 1 => {
+                    let piece_sequence = self
+                        .settings
+                        .newgame
+                        .custom_piece_sequence
+                        .clone()
+                        .unwrap_or_default();
+                    custom_text_input = Some((CustomTextInput::Pieces, piece_sequence, None));
+                }
+
+                // Rename stored custom game.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('r' | 'R'),
+                    kind: Press,
+                    ..
+                }) if preset_idxs.contains(&selected) => {
+                    renaming = Some((
+                        self.settings.custom_game_slotmachine.slots[selected - first_preset_idx]
+                            .0
+                            .clone(),
+                        None,
+                    ));
+                }
+
+                // Show daily challenge results.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('s' | 'S'),
+                    kind: Press,
+                    ..
+                }) if selected == daily_idx => {
+                    break Ok(MenuUpdate::Push(Menu::DailyResults {
+                        cursor_pos: 0,
+                        camera_pos: 0,
+                    }));
+                }
+
                 // Secret - This unlocks things.
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('u' | 'U'),
@@ -660,7 +976,7 @@ impl<T: Write> Application<T> {
                 _ => {}
             }
 
-            if immediately_start_new_game {
+            if immediately_start_new_game && !broken_mode_file_idxs.contains(&selected) {
                 let GameplaySettings {
                     rotsys: rotation_system,
                     randomizer: tetromino_generator,
@@ -699,10 +1015,23 @@ impl<T: Write> Application<T> {
 
                     let preset_game = build(&builder);
 
+                    // The first daily challenge game of the day is the official attempt.
+                    let official_daily = selected == daily_idx && !daily_attempted;
+                    if official_daily {
+                        self.scores_and_replays.daily_last_attempted = Some(today.to_string());
+                    }
+
                     let preset_game_meta_data = GameMetaData {
                         datetime: chrono::Utc::now().format("%Y-%m-%d_%H:%M").to_string(),
                         title: title.to_owned(),
                         comparison_stat: *stat_and_order_desc,
+                        forked_from: None,
+                        used_undo: false,
+                        official_daily,
+                        tool_assisted: false,
+                        bot_played: false,
+                        used_solver: false,
+                        puzzle: is_puzzle(&preset_game),
                     };
 
                     let fresh_input_history = UncompressedInputHistory::default();
@@ -711,73 +1040,26 @@ impl<T: Write> Application<T> {
                 } else if selected == selection_len - 2 {
                     // Load saved game.
                     // SAFETY: we an only get into this case if save exists!...
-                    let GameSave {
-                        game_meta_data,
-                        game_restoration_data,
-                        inputs_to_load,
-                    } = &self.game_saves.1.get(self.game_saves.0).unwrap();
-
-                    let restored_game = game_restoration_data.restore(*inputs_to_load);
-
-                    let mut restored_game_meta_data = game_meta_data.clone();
-                    // Mark restored game as such.
-                    restored_game_meta_data.title.push('\'');
-
-                    let restored_input_history = game_restoration_data
-                        .input_history
-                        .iter()
-                        .take(*inputs_to_load)
-                        .copied()
-                        .collect();
-
-                    (
-                        restored_game_meta_data,
-                        restored_game,
-                        restored_input_history,
-                    )
+                    self.game_saves.1.get(self.game_saves.0).unwrap().load()
                 } else {
                     // Build custom game.
-                    let n = &self.settings.newgame;
-
-                    builder
-                        .fall_delay_params(n.custom_fall_delay_params)
-                        .game_limits(match n.custom_win_condition {
-                            Some(stat) => GameLimits::single(stat, true),
-                            None => GameLimits::new(),
-                        });
+                    let preset = self.settings.newgame.custom_game_preset();
 
-                    // Make lock delay decrease if fall delay was chosen to decrease.
-                    if !n.custom_fall_delay_params.is_constant() {
-                        builder.lock_delay_params(DelayParameters::standard_lock());
-                    }
-
-                    // Optionally load custom seed.
-                    if let Some(seed) = n.custom_seed {
-                        builder.seed(seed);
-                    }
-
-                    // Optionally load custom board.
-                    let new_custom_game = if let Some(encoded_board) = &n.custom_encoded_board {
-                        game_modifiers::StartBoard::build(&builder, encoded_board.clone())
-                    // Otherwise just build a normal custom game.
-                    } else {
-                        builder.build()
-                    };
+                    let new_custom_game = preset.build(&builder);
 
-                    let title = match n.custom_win_condition {
-                        Some(stat) => match stat {
-                            Stat::TimeElapsed(duration) => format!("Time-{}s", duration.as_secs()),
-                            Stat::PiecesLocked(p) => format!("Pieces-{p}"),
-                            Stat::LinesCleared(l) => format!("Lines-{l}"),
-                            Stat::PointsScored(s) => format!("Score-{s}"),
-                        },
-                        None => "Limitless".to_owned(),
-                    };
+                    let title = preset.title();
 
                     let custom_game_meta_data = GameMetaData {
                         datetime: chrono::Utc::now().format("%Y-%m-%d_%H:%M").to_string(),
                         title,
                         comparison_stat: (Stat::PointsScored(0), false),
+                        forked_from: None,
+                        used_undo: false,
+                        official_daily: false,
+                        tool_assisted: false,
+                        bot_played: false,
+                        used_solver: false,
+                        puzzle: is_puzzle(&new_custom_game),
                     };
                     let fresh_input_history = UncompressedInputHistory::default();
                     (custom_game_meta_data, new_custom_game, fresh_input_history)
@@ -808,6 +1090,7 @@ impl<T: Write> Application<T> {
                     game_input_history,
                     game_meta_data,
                     game_renderer: game_renderer.into(),
+                    split_recorder: None,
                 }));
             }
         }
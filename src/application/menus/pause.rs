@@ -6,7 +6,10 @@ use crate::application::{
 };
 
 impl<T: Write> Application<T> {
-    pub(in crate::application) fn run_menu_pause(&mut self) -> io::Result<MenuUpdate> {
+    pub(in crate::application) fn run_menu_pause(
+        &mut self,
+        board_fumen: String,
+    ) -> io::Result<MenuUpdate> {
         let selection = vec![
             Menu::NewGame,
             Menu::Settings,
@@ -18,6 +21,7 @@ impl<T: Write> Application<T> {
             Menu::About,
             Menu::Quit,
         ];
-        self.generic_menu("Game Paused", selection)
+        let notes = vec!["Board as fumen:".to_owned(), board_fumen];
+        self.generic_menu("Game Paused", selection, notes)
     }
 }
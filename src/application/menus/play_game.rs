@@ -11,18 +11,23 @@ use crossterm::{
     ExecutableCommand,
 };
 use falling_tetromino_engine::{
-    Button, Game, GameEndCause, InGameTime, Input, Notification, Phase, UpdateGameError,
+    Button, Game, GameEndCause, InGameTime, Input, Notification, NotificationFeed, Phase,
+    UpdateGameError,
 };
 
 use crate::{
     application::{
         menus::{Menu, MenuUpdate},
-        Application, CompressedInputHistory, GameMetaData, GameRestorationData, GameSave,
-        ScoreEntry, Statistics, UncompressedInputHistory,
+        Application, CompressedInputHistory, FumenImport, GameMetaData, GameRestorationData,
+        NewGameSettings, ScoreEntry, SplitMilestones, SplitRecorder, Statistics,
+        UncompressedInputHistory,
     },
+    bot_interface::BotInterface,
     fmt_helpers::get_play_keybinds_legend,
+    game_modes::game_modifiers::{PieceSequence, Puzzle, Sandbox, SandboxEdit},
     game_renderers::{Renderer, TetroTUIRenderer},
     live_input_handler::{self, LiveTermSignal},
+    solver,
 };
 
 impl<T: Write> Application<T> {
@@ -32,6 +37,7 @@ impl<T: Write> Application<T> {
         game_input_history: &mut UncompressedInputHistory,
         game_meta_data: &mut GameMetaData,
         game_renderer: &mut TetroTUIRenderer,
+        split_recorder: &mut Option<Box<SplitRecorder>>,
     ) -> io::Result<MenuUpdate> {
         /* Our game loop recipe looks like this:
           * Enter 'update_and_render loop:
@@ -40,7 +46,7 @@ impl<T: Write> Application<T> {
               + Use player input to update game.
               + If budget ran out, break loop.
             - Set 'latest refresh' variable to ::now().
-            - Do game.update().
+            - Do Sandbox::update_game(game, ).
               ** Note that in-game time at time of update can be determined with either
                  -- `duration elapsed IRL - duration paused`,
                  -- `in-game time before entering loop + in-game time elapsed since loop entered`.
@@ -88,6 +94,59 @@ impl<T: Write> Application<T> {
 
         let keybinds_legend = get_play_keybinds_legend(self.settings.keybinds());
 
+        // Sandbox games get an extra input layer to edit the game by hand.
+        let mut is_sandbox = game
+            .modifiers
+            .iter()
+            .any(|modifier| modifier.id() == Sandbox::MOD_ID);
+
+        // Puzzle games can show a hint on how to solve the stage.
+        let mut is_puzzle = game
+            .modifiers
+            .iter()
+            .any(|modifier| modifier.id() == Puzzle::MOD_ID);
+
+        // Position of the cell being edited, if game buttons are used to edit the board instead of playing.
+        let mut edit_cursor: Option<(usize, usize)> = None;
+        game_renderer.set_edit_cursor(edit_cursor);
+
+        // Start external bot if one was requested.
+        let mut bot = None;
+        if let Some(bot_cmd) = &self.temp_data.bot_cmd {
+            if !game.has_ended() {
+                match BotInterface::spawn(bot_cmd) {
+                    Ok(bot_interface) => {
+                        bot = Some(bot_interface);
+                        game_meta_data.bot_played = true;
+                    }
+                    Err(e) => game_renderer.push_game_notification_feed([(
+                        Notification::Custom(format!("Could not start bot: {e}")),
+                        game.state().time,
+                    )]),
+                }
+            }
+        }
+
+        // Prepare comparing pace against best game, if the renderer does not already do so.
+        if !game_renderer.has_pace_splits() {
+            if let Some((SplitMilestones::EveryNLines(n), best_splits)) =
+                self.best_splits(game_meta_data)
+            {
+                game_renderer.set_pace_splits(n, best_splits);
+            }
+        }
+
+        // Take split times as the game goes, if it is played for time.
+        if split_recorder.is_none() {
+            *split_recorder = SplitMilestones::for_game(game_meta_data).map(|split_milestones| {
+                Box::new(SplitRecorder::resume(
+                    split_milestones,
+                    game,
+                    game_input_history,
+                ))
+            });
+        }
+
         // FPS counter.
         let mut renders_per_second_counter = 0u32;
         let mut renders_per_second_counter_start_time = Instant::now();
@@ -96,6 +155,9 @@ impl<T: Write> Application<T> {
         let (x_main, y_main) = Application::<T>::fetch_main_xy();
         game_renderer.set_render_offset(usize::from(x_main), usize::from(y_main));
         game_renderer.reset_view_diff_state();
+        if let Some(split_recorder) = split_recorder.as_deref() {
+            game_renderer.set_current_splits(split_recorder.splits());
+        }
         game_renderer.render(
             &mut self.term,
             game,
@@ -124,37 +186,39 @@ impl<T: Write> Application<T> {
             // Start new iteration of [render->input->] loop.
 
             if let Phase::GameEnd { cause, is_win } = game.phase() {
-                self.statistics.total_games_ended += 1;
+                // Make sure games the bot played in are never ranked.
+                game_meta_data.bot_played |= bot.is_some();
+
+                if game_meta_data.is_ranked() {
+                    self.statistics.total_games_ended += 1;
+                }
 
                 // Game ended, cannot actually continue playing;
                 // Convert to scoreboard entry and return appropriate game-ended menu.
-                let scores_entry = ScoreEntry {
-                    game_meta_data: game_meta_data.clone(),
-                    is_win: *is_win,
-                    end_cause: cause.clone(),
-                    time_elapsed: game.state().time,
-                    pieces_locked: game.state().pieces_locked,
-                    lineclears: game.state().lineclears,
-                    fall_delay_reached: game.state().fall_delay,
-                    lock_delay_reached: (game
-                        .state()
-                        .fall_delay_lowerbound_hit_at_n_lineclears
-                        .is_some()
-                        && !game.config.lock_delay_params.is_constant())
-                    .then_some(game.state().lock_delay),
-                    points_scored: game.state().points,
-                };
-
-                let compressed_game_input_history = CompressedInputHistory::new(game_input_history);
                 let forfeit =
                     matches!(cause, GameEndCause::Forfeit { .. }).then_some(game.state().time);
 
                 let game_restoration_data =
-                    GameRestorationData::new(game, compressed_game_input_history, forfeit);
+                    GameRestorationData::new(game, game_input_history.clone(), forfeit);
+
+                let scores_entry = ScoreEntry::new(
+                    game,
+                    game_meta_data.clone(),
+                    split_recorder
+                        .take()
+                        .map(|split_recorder| split_recorder.finish()),
+                );
 
-                self.scores_and_replays
-                    .entries
-                    .push((scores_entry.clone(), Some(game_restoration_data)));
+                let game_restoration_data =
+                    game_restoration_data.map(|h| CompressedInputHistory::new(&h));
+
+                // The official daily attempt goes into its own scoreboard.
+                let entries = if game_meta_data.official_daily && game_meta_data.is_ranked() {
+                    &mut self.scores_and_replays.daily_entries
+                } else {
+                    &mut self.scores_and_replays.entries
+                };
+                entries.push((scores_entry.clone(), Some(game_restoration_data)));
 
                 let game_scoring = Box::new(scores_entry);
 
@@ -189,6 +253,57 @@ impl<T: Write> Application<T> {
                         match signal {
                             // Found a recognized game input: use it.
                             LiveTermSignal::RecognizedButton(mut button, key_event_kind) => {
+                                // The bot is in control of the game.
+                                if bot.is_some() {
+                                    continue 'wait;
+                                }
+
+                                // Editing the board: move the cursor or toggle the cell under it.
+                                if let Some((x, y)) = &mut edit_cursor {
+                                    if !matches!(
+                                        key_event_kind,
+                                        KeyEventKind::Press | KeyEventKind::Repeat
+                                    ) {
+                                        continue 'wait;
+                                    }
+
+                                    match button {
+                                        Button::MoveLeft | Button::TeleLeft => {
+                                            *x = x.saturating_sub(1)
+                                        }
+                                        Button::MoveRight | Button::TeleRight => {
+                                            *x = (*x + 1).min(Game::WIDTH - 1)
+                                        }
+                                        Button::DropSoft | Button::TeleDown => {
+                                            *y = y.saturating_sub(1)
+                                        }
+                                        Button::DropHard => {
+                                            *y = (*y + 1).min(Game::LOCK_OUT_HEIGHT - 1)
+                                        }
+                                        Button::RotateLeft
+                                        | Button::RotateRight
+                                        | Button::Rotate180
+                                        | Button::HoldPiece => {
+                                            match do_sandbox_edit(
+                                                game,
+                                                game_input_history,
+                                                SandboxEdit::ToggleCell(*x, *y),
+                                            ) {
+                                                Ok(msgs) => {
+                                                    game_renderer.push_game_notification_feed(msgs)
+                                                }
+                                                Err(UpdateGameError::AlreadyEnded) => break 'wait,
+                                                Err(UpdateGameError::TargetTimeInPast) => {
+                                                    unreachable!()
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    game_renderer.set_edit_cursor(edit_cursor);
+                                    continue 'wait;
+                                }
+
                                 // We first calculate the intended time at time of reaching here.
                                 let update_target_time = ingametime_when_game_loop_entered
                                     + timestamp.saturating_duration_since(time_game_loop_entered);
@@ -256,9 +371,16 @@ impl<T: Write> Application<T> {
 
                                     game_input_history.push((update_target_time, player_input));
 
-                                    match game.update(update_target_time, Some(player_input)) {
+                                    match Sandbox::update_game(
+                                        game,
+                                        update_target_time,
+                                        Some(player_input),
+                                    ) {
                                         Ok(msgs) => {
                                             temp_statistics.accumulate_from_feed(&msgs);
+                                            if let Some(split_recorder) = split_recorder.as_mut() {
+                                                split_recorder.record(&msgs);
+                                            }
                                             game_renderer.push_game_notification_feed(msgs)
                                         }
                                         Err(UpdateGameError::AlreadyEnded) => break 'wait,
@@ -305,9 +427,16 @@ impl<T: Write> Application<T> {
 
                                     game_input_history.push((update_target_time, button_change));
 
-                                    match game.update(update_target_time, Some(button_change)) {
+                                    match Sandbox::update_game(
+                                        game,
+                                        update_target_time,
+                                        Some(button_change),
+                                    ) {
                                         Ok(msgs) => {
                                             temp_statistics.accumulate_from_feed(&msgs);
+                                            if let Some(split_recorder) = split_recorder.as_mut() {
+                                                split_recorder.record(&msgs);
+                                            }
                                             game_renderer.push_game_notification_feed(msgs);
                                         }
                                         Err(UpdateGameError::AlreadyEnded) => break 'wait,
@@ -319,12 +448,18 @@ impl<T: Write> Application<T> {
 
                                     game_input_history.push((update_target_time, button_change));
 
-                                    let update_result =
-                                        game.update(update_target_time, Some(button_change));
+                                    let update_result = Sandbox::update_game(
+                                        game,
+                                        update_target_time,
+                                        Some(button_change),
+                                    );
 
                                     match update_result {
                                         Ok(msgs) => {
                                             temp_statistics.accumulate_from_feed(&msgs);
+                                            if let Some(split_recorder) = split_recorder.as_mut() {
+                                                split_recorder.record(&msgs);
+                                            }
                                             game_renderer.push_game_notification_feed(msgs)
                                         }
                                         Err(UpdateGameError::AlreadyEnded) => break 'wait,
@@ -352,7 +487,12 @@ impl<T: Write> Application<T> {
                                             // [Esc]: Pause.
                                             (KeyCode::Esc, _) => {
                                                 break 'update_and_render MenuUpdate::Push(
-                                                    Menu::Pause,
+                                                    Menu::Pause {
+                                                        board_fumen: NewGameSettings::encode_fumen(
+                                                            &game.state().board,
+                                                            game.phase().piece(),
+                                                        ),
+                                                    },
                                                 );
                                             }
 
@@ -368,6 +508,11 @@ impl<T: Write> Application<T> {
                                                 match game.forfeit() {
                                                     Ok(msgs) => {
                                                         temp_statistics.accumulate_from_feed(&msgs);
+                                                        if let Some(split_recorder) =
+                                                            split_recorder.as_mut()
+                                                        {
+                                                            split_recorder.record(&msgs);
+                                                        }
                                                         game_renderer
                                                             .push_game_notification_feed(msgs);
                                                     }
@@ -384,61 +529,69 @@ impl<T: Write> Application<T> {
 
                                             // [Ctrl+S]: Store savepoint.
                                             (KeyCode::Char('s' | 'S'), KeyModifiers::CONTROL) => {
-                                                self.game_saves = (
-                                                    0,
-                                                    vec![GameSave {
-                                                        game_meta_data: game_meta_data.clone(),
-                                                        game_restoration_data:
-                                                            GameRestorationData::new(
-                                                                game,
-                                                                game_input_history.clone(),
-                                                                matches!(
-                                                                    game.phase(),
-                                                                    Phase::GameEnd {
-                                                                        cause:
-                                                                            GameEndCause::Forfeit { .. },
-                                                                        ..
-                                                                    }
-                                                                )
-                                                                .then_some(game.state().time),
-                                                            ),
-                                                        inputs_to_load: game_input_history.len(),
-                                                    }],
+                                                let name = self.store_game_save(
+                                                    game_meta_data,
+                                                    game,
+                                                    game_input_history.clone(),
+                                                    game_input_history.len(),
                                                 );
 
                                                 game_renderer.push_game_notification_feed([(
-                                                    Notification::Custom(
-                                                        "(Stored savepoint)".to_owned(),
-                                                    ),
+                                                    Notification::Custom(format!(
+                                                        "(Stored savepoint '{name}')"
+                                                    )),
                                                     game.state().time,
                                                 )]);
                                             }
 
                                             // [Ctrl+L]: Load savepoint.
-                                            (KeyCode::Char('l' | 'L'), KeyModifiers::CONTROL) => {
-                                                let GameSave {
-                                                    game_meta_data: saved_meta_data,
-                                                    game_restoration_data,
-                                                    inputs_to_load,
-                                                } = &self
+                                            (KeyCode::Char('l' | 'L'), KeyModifiers::CONTROL)
+                                                if self
                                                     .game_saves
                                                     .1
                                                     .get(self.game_saves.0)
-                                                    .unwrap();
-
-                                                *game =
-                                                    game_restoration_data.restore(*inputs_to_load);
-
-                                                *game_meta_data = saved_meta_data.clone();
-                                                // Mark restored game as such.
-                                                game_meta_data.title.push('\'');
+                                                    .is_some() =>
+                                            {
+                                                (*game_meta_data, *game, *game_input_history) =
+                                                    self.game_saves.1[self.game_saves.0].load();
+
+                                                is_sandbox =
+                                                    game.modifiers.iter().any(|modifier| {
+                                                        modifier.id() == Sandbox::MOD_ID
+                                                    });
+                                                if !is_sandbox {
+                                                    edit_cursor = None;
+                                                    game_renderer.set_edit_cursor(edit_cursor);
+                                                }
+                                                is_puzzle = game.modifiers.iter().any(|modifier| {
+                                                    modifier.id() == Puzzle::MOD_ID
+                                                });
+
+                                                // The bot keeps playing the loaded game.
+                                                game_meta_data.bot_played |= bot.is_some();
+                                                if let Some(bot) = &mut bot {
+                                                    bot.restart();
+                                                }
 
-                                                *game_input_history = game_restoration_data
-                                                    .input_history
-                                                    .iter()
-                                                    .take(*inputs_to_load)
-                                                    .copied()
-                                                    .collect();
+                                                // Splits of the abandoned game are of no concern, and the loaded one may be of another mode.
+                                                *split_recorder = SplitMilestones::for_game(
+                                                    game_meta_data,
+                                                )
+                                                .map(|split_milestones| {
+                                                    Box::new(SplitRecorder::resume(
+                                                        split_milestones,
+                                                        game,
+                                                        game_input_history,
+                                                    ))
+                                                });
+                                                game_renderer.clear_pace_splits();
+                                                if let Some((
+                                                    SplitMilestones::EveryNLines(n),
+                                                    best_splits,
+                                                )) = self.best_splits(game_meta_data)
+                                                {
+                                                    game_renderer.set_pace_splits(n, best_splits);
+                                                }
 
                                                 game_renderer.reset_game_associated_state();
                                                 game_renderer.push_game_notification_feed([(
@@ -459,6 +612,202 @@ impl<T: Write> Application<T> {
                                                 time_game_loop_entered = Instant::now();
                                             }
 
+                                            // [Ctrl+Z]: Step back a piece (sandbox).
+                                            (KeyCode::Char('z' | 'Z'), KeyModifiers::CONTROL)
+                                                if is_sandbox =>
+                                            {
+                                                match do_sandbox_edit(
+                                                    game,
+                                                    game_input_history,
+                                                    SandboxEdit::StepBack,
+                                                ) {
+                                                    Ok(msgs) => {
+                                                        game_renderer
+                                                            .push_game_notification_feed(msgs);
+                                                    }
+                                                    Err(UpdateGameError::AlreadyEnded) => {
+                                                        break 'wait
+                                                    }
+                                                    Err(UpdateGameError::TargetTimeInPast) => {
+                                                        unreachable!()
+                                                    }
+                                                }
+                                            }
+
+                                            // [Alt+I/O/T/S/Z/J/L]: Choose next piece (sandbox).
+                                            (KeyCode::Char(c), KeyModifiers::ALT)
+                                                if is_sandbox && "iotszjlIOTSZJL".contains(c) =>
+                                            {
+                                                let (tetrominos, _) =
+                                                    PieceSequence::parse(&c.to_string()).unwrap();
+
+                                                match do_sandbox_edit(
+                                                    game,
+                                                    game_input_history,
+                                                    SandboxEdit::SetNext(tetrominos[0]),
+                                                ) {
+                                                    Ok(msgs) => {
+                                                        game_renderer
+                                                            .push_game_notification_feed(msgs);
+                                                    }
+                                                    Err(UpdateGameError::AlreadyEnded) => {
+                                                        break 'wait
+                                                    }
+                                                    Err(UpdateGameError::TargetTimeInPast) => {
+                                                        unreachable!()
+                                                    }
+                                                }
+                                            }
+
+                                            // [Alt+E]: Toggle editing the board (sandbox).
+                                            (KeyCode::Char('e' | 'E'), KeyModifiers::ALT)
+                                                if is_sandbox =>
+                                            {
+                                                edit_cursor = match edit_cursor {
+                                                    Some(_) => None,
+                                                    None => Some((0, 0)),
+                                                };
+                                                game_renderer.set_edit_cursor(edit_cursor);
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(
+                                                        if edit_cursor.is_some() {
+                                                            "Editing board [Alt+E]"
+                                                        } else {
+                                                            "Done editing board [Alt+E]"
+                                                        }
+                                                        .to_owned(),
+                                                    ),
+                                                    game.state().time,
+                                                )]);
+                                            }
+
+                                            // [Alt+H]: Show where to place the piece to solve the stage (puzzle).
+                                            (KeyCode::Char('h' | 'H'), KeyModifiers::ALT)
+                                                if is_puzzle =>
+                                            {
+                                                game_meta_data.used_solver = true;
+
+                                                // The pieces left in the stage are the one in play and those in the preview.
+                                                let solution =
+                                                    game.phase().piece().and_then(|piece| {
+                                                        let pieces = std::iter::once(*piece)
+                                                            .chain(
+                                                                game.state()
+                                                                    .piece_preview
+                                                                    .iter()
+                                                                    .map(|tet| {
+                                                                        tet.piece_spawn_state()
+                                                                    }),
+                                                            )
+                                                            .collect::<Vec<_>>();
+                                                        solver::solve_puzzle(
+                                                            &game.state().board,
+                                                            &pieces,
+                                                            &game.config.rotation_system,
+                                                        )
+                                                    });
+
+                                                let text = match solution {
+                                                    Some(solution) => {
+                                                        game_renderer.set_ghost_tiles(
+                                                            solution[0].tiles().to_vec(),
+                                                        );
+                                                        "(Hint: place piece as shown)"
+                                                    }
+                                                    None => "(Hint: no solution from here)",
+                                                };
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(text.to_owned()),
+                                                    game.state().time,
+                                                )]);
+                                            }
+
+                                            // [Alt+A]: Look for a perfect clear with the pieces visible (unranked games).
+                                            (KeyCode::Char('a' | 'A'), KeyModifiers::ALT)
+                                                if is_sandbox || !game_meta_data.is_ranked() =>
+                                            {
+                                                let text = match solver::find_perfect_clear(game) {
+                                                    Some(solution) => {
+                                                        let text = format!(
+                                                            "(Perfect clear in {} pieces)",
+                                                            solution.len()
+                                                        );
+                                                        game_renderer.set_ghost_tiles(
+                                                            solver::placement_tiles(
+                                                                &game.state().board,
+                                                                &solution,
+                                                            ),
+                                                        );
+                                                        text
+                                                    }
+                                                    None => {
+                                                        "(No perfect clear with visible pieces)"
+                                                            .to_owned()
+                                                    }
+                                                };
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(text),
+                                                    game.state().time,
+                                                )]);
+                                            }
+
+                                            // [Ctrl+Z]: Undo last piece.
+                                            (KeyCode::Char('z' | 'Z'), KeyModifiers::CONTROL) => {
+                                                let game_restoration_data =
+                                                    GameRestorationData::new(
+                                                        game,
+                                                        game_input_history.clone(),
+                                                        None,
+                                                    );
+
+                                                let Some(inputs_to_load) = game_restoration_data
+                                                    .inputs_before_last_lock(game.state().time)
+                                                else {
+                                                    game_renderer.push_game_notification_feed([(
+                                                        Notification::Custom(
+                                                            "(Nothing to undo)".to_owned(),
+                                                        ),
+                                                        game.state().time,
+                                                    )]);
+                                                    continue 'wait;
+                                                };
+
+                                                *game =
+                                                    game_restoration_data.restore(inputs_to_load);
+                                                game_input_history.truncate(inputs_to_load);
+                                                game_meta_data.used_undo = true;
+                                                if let Some(split_recorder) =
+                                                    split_recorder.as_mut()
+                                                {
+                                                    split_recorder.set_back(game);
+                                                }
+
+                                                if let Some(bot) = &mut bot {
+                                                    bot.restart();
+                                                }
+
+                                                game_renderer.reset_game_associated_state();
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(
+                                                        "(Undid last piece)".to_owned(),
+                                                    ),
+                                                    game.state().time,
+                                                )]);
+
+                                                // Like when loading a savepoint, adapt the game loop state itself.
+                                                self.statistics.total_play_time += Instant::now()
+                                                    .saturating_duration_since(
+                                                        time_game_loop_entered,
+                                                    );
+
+                                                ingametime_when_game_loop_entered =
+                                                    game.state().time;
+                                                time_game_loop_entered = Instant::now();
+                                            }
+
                                             // [Ctrl+E]: Store seed.
                                             (KeyCode::Char('e' | 'E'), KeyModifiers::CONTROL) => {
                                                 self.settings.newgame.custom_seed =
@@ -473,6 +822,22 @@ impl<T: Write> Application<T> {
                                                 )]);
                                             }
 
+                                            // [Ctrl+B]: Store board as custom start board.
+                                            (KeyCode::Char('b' | 'B'), KeyModifiers::CONTROL) => {
+                                                self.settings.newgame.load_fumen(
+                                                    FumenImport::Board(Box::new(
+                                                        game.state().board,
+                                                    )),
+                                                );
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(
+                                                        "(Board stored as start board)".to_owned(),
+                                                    ),
+                                                    game.state().time,
+                                                )]);
+                                            }
+
                                             // [Ctrl+Alt+B]: (Un-)Blindfold.
                                             (KeyCode::Char('b' | 'B'), _)
                                                 if {
@@ -537,7 +902,12 @@ impl<T: Write> Application<T> {
                                 // FIXME: This 'extremely' rare error is currently fixed by pausing the game
                                 // which means no extra work for us and just one extra step for the user.
                                 // But maybe properly try restarting the thread manually?...
-                                break 'update_and_render MenuUpdate::Push(Menu::Pause);
+                                break 'update_and_render MenuUpdate::Push(Menu::Pause {
+                                    board_fumen: NewGameSettings::encode_fumen(
+                                        &game.state().board,
+                                        game.phase().piece(),
+                                    ),
+                                });
                             }
                         }
                     }
@@ -550,10 +920,13 @@ impl<T: Write> Application<T> {
             let update_target_time = ingametime_when_game_loop_entered
                 + now.saturating_duration_since(time_game_loop_entered);
 
-            match game.update(update_target_time, None) {
+            match Sandbox::update_game(game, update_target_time, None) {
                 // Update.
                 Ok(msgs) => {
                     temp_statistics.accumulate_from_feed(&msgs);
+                    if let Some(split_recorder) = split_recorder.as_mut() {
+                        split_recorder.record(&msgs);
+                    }
                     game_renderer.push_game_notification_feed(msgs)
                 }
 
@@ -562,7 +935,51 @@ impl<T: Write> Application<T> {
                 Err(UpdateGameError::AlreadyEnded | UpdateGameError::TargetTimeInPast) => {}
             }
 
+            // Let the bot act: it presses and immediately releases buttons, all at the current time.
+            if let Some(bot) = &mut bot {
+                let (buttons, bot_msgs) = bot.poll(game);
+
+                let bot_msgs_time = game.state().time;
+                game_renderer.push_game_notification_feed(
+                    bot_msgs
+                        .into_iter()
+                        .map(|msg| (Notification::Custom(msg), bot_msgs_time)),
+                );
+
+                // Round up to milliseconds, like player inputs (see above).
+                let nanos = game.state().time.as_nanos();
+                const NANOS_PER_MILLI: u128 = 1_000_000;
+                let bot_input_time =
+                    InGameTime::from_millis(nanos.div_ceil(NANOS_PER_MILLI) as u64);
+
+                'bot_inputs: for button in buttons {
+                    for button_change in [Input::Activate(button), Input::Deactivate(button)] {
+                        // Modifiers may have moved the game's time past the rounded one.
+                        let bot_input_time = bot_input_time.max(game.state().time);
+
+                        game_input_history.push((bot_input_time, button_change));
+
+                        match Sandbox::update_game(game, bot_input_time, Some(button_change)) {
+                            Ok(msgs) => {
+                                bot.observe_feed(&msgs);
+                                temp_statistics.accumulate_from_feed(&msgs);
+                                if let Some(split_recorder) = split_recorder.as_mut() {
+                                    split_recorder.record(&msgs);
+                                }
+                                game_renderer.push_game_notification_feed(msgs);
+                            }
+                            Err(
+                                UpdateGameError::AlreadyEnded | UpdateGameError::TargetTimeInPast,
+                            ) => break 'bot_inputs,
+                        }
+                    }
+                }
+            }
+
             // Render current state of the game.
+            if let Some(split_recorder) = split_recorder.as_deref() {
+                game_renderer.set_current_splits(split_recorder.splits());
+            }
             game_renderer.render(
                 &mut self.term,
                 game,
@@ -632,12 +1049,16 @@ impl<T: Write> Application<T> {
                 if game.state().active_buttons[button].is_some() {
                     let button_change = Input::Deactivate(button);
 
-                    let update_result = game.update(unpress_time, Some(button_change));
+                    let update_result =
+                        Sandbox::update_game(game, unpress_time, Some(button_change));
 
                     game_input_history.push((unpress_time, button_change));
                     match update_result {
                         Ok(msgs) => {
                             temp_statistics.accumulate_from_feed(&msgs);
+                            if let Some(split_recorder) = split_recorder.as_mut() {
+                                split_recorder.record(&msgs);
+                            }
                             game_renderer.push_game_notification_feed(msgs);
                         }
                         Err(UpdateGameError::AlreadyEnded) => break 'button_unpressing,
@@ -650,9 +1071,11 @@ impl<T: Write> Application<T> {
         self.statistics.total_play_time +=
             Instant::now().saturating_duration_since(time_game_loop_entered);
 
-        if !Statistics::BLACKLIST_TITLE_PREFIXES
-            .iter()
-            .any(|prefix| game_meta_data.title.starts_with(prefix))
+        if game_meta_data.is_ranked()
+            && !game_meta_data.puzzle
+            && !Statistics::BLACKLIST_TITLE_PREFIXES
+                .iter()
+                .any(|prefix| game_meta_data.base_mode_title().starts_with(prefix))
         {
             self.statistics.accumulate(&temp_statistics);
         }
@@ -660,3 +1083,37 @@ impl<T: Write> Application<T> {
         Ok(menu_update)
     }
 }
+
+/// Have the sandbox of the game carry out an edit at the current time.
+///
+/// The edit is added to the arguments of the sandbox, with which the game is restored, so that replays reproduce it.
+fn do_sandbox_edit(
+    game: &mut Game,
+    game_input_history: &UncompressedInputHistory,
+    edit: SandboxEdit,
+) -> Result<NotificationFeed, UpdateGameError> {
+    if game.has_ended() {
+        return Err(UpdateGameError::AlreadyEnded);
+    }
+
+    // Round up to milliseconds, like player inputs.
+    let nanos = game.state().time.as_nanos();
+    const NANOS_PER_MILLI: u128 = 1_000_000;
+    let edit_time = InGameTime::from_millis(nanos.div_ceil(NANOS_PER_MILLI) as u64);
+
+    let mut game_restoration_data =
+        GameRestorationData::new(game, game_input_history.clone(), None);
+    for (mod_id, mod_args) in &mut game_restoration_data.mod_ids_args {
+        if mod_id == Sandbox::MOD_ID {
+            Sandbox::add_edit(mod_args, edit_time, edit);
+        }
+    }
+
+    *game = game_restoration_data.restore(game_input_history.len());
+    let mut msgs = Sandbox::update_game(game, edit_time, None)?;
+
+    // Catching up with the time of the edit repeats what has happened since the last input.
+    msgs.retain(|(_notification, time)| edit_time <= *time);
+
+    Ok(msgs)
+}
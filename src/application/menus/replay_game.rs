@@ -8,27 +8,238 @@ use crossterm::{
     cursor::MoveTo,
     event::{self, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     style::{Print, PrintStyledContent, Stylize},
-    terminal::Clear,
-    ExecutableCommand,
+    terminal::{self, Clear, ClearType},
+    ExecutableCommand, QueueableCommand,
 };
 use falling_tetromino_engine::{
-    Game, GameEndCause, InGameTime, Input, Notification, Phase, UpdateGameError,
+    Button, Game, InGameTime, Input, Notification, NotificationFeed, UpdateGameError,
 };
 
 use crate::{
     application::{
         menus::{Menu, MenuUpdate},
-        Application, GameMetaData, GameRestorationData, GameSave, UncompressedInputHistory,
+        Application, GameMetaData, GameRestorationData, GameSaveAnchor, ReplayFork,
+        UncompressedInputHistory,
     },
     fmt_helpers::{fmt_duration, replay_keybinds_legend},
+    game_modes::game_modifiers::Sandbox,
     game_renderers::{Renderer, TetroTUIRenderer},
     keybinds::Keybinds,
     live_input_handler::{self, LiveTermSignal},
+    solver,
 };
 
-struct GameSaveAnchor {
-    game: Game,
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(in crate::application) enum ReplayEventKind {
+    Hold,
+    Tetra,
+    Spin,
+}
+
+impl ReplayEventKind {
+    const VARIANTS: [Self; 3] = [Self::Hold, Self::Tetra, Self::Spin];
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum SeekPrompt {
+    Piece,
+    LineClear,
+}
+
+/// Points in time of interesting events in a replay, to be able to seek to them.
+#[derive(Default, Debug)]
+pub(in crate::application) struct ReplayIndex {
+    piece_lock_times: Vec<InGameTime>,
+    // Stores the total number of lines cleared after each line clear.
+    line_clear_times: Vec<(u32, InGameTime)>,
+    events: Vec<(ReplayEventKind, InGameTime)>,
+}
+
+impl ReplayIndex {
+    fn index_feed(&mut self, feed: &NotificationFeed) {
+        for (notification, time) in feed {
+            match notification {
+                Notification::PieceLocked { .. } => self.piece_lock_times.push(*time),
+                Notification::LinesClearing { y_coords, .. } => {
+                    let lines_total = self.line_clear_times.last().map_or(0, |(n, _)| *n)
+                        + u32::try_from(y_coords.len()).unwrap();
+                    self.line_clear_times.push((lines_total, *time));
+                }
+                Notification::Accolade {
+                    lineclears,
+                    is_spin,
+                    ..
+                } => {
+                    if *lineclears >= 4 {
+                        self.events.push((ReplayEventKind::Tetra, *time));
+                    }
+                    if *is_spin {
+                        self.events.push((ReplayEventKind::Spin, *time));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// When piece number `n` (starting at 1) became the next to be played.
+    pub(in crate::application) fn time_of_piece(&self, n: usize) -> Option<InGameTime> {
+        match n {
+            0 | 1 => Some(InGameTime::ZERO),
+            n => self.piece_lock_times.get(n - 2).copied(),
+        }
+    }
+
+    /// When at least `n` lines were cleared in total.
+    fn time_after_lines(&self, n: u32) -> Option<InGameTime> {
+        self.line_clear_times
+            .iter()
+            .find(|(lines_total, _)| n <= *lines_total)
+            .map(|(_, time)| *time)
+    }
+
+    fn time_of_event(
+        &self,
+        kind: ReplayEventKind,
+        current_time: InGameTime,
+        forward: bool,
+    ) -> Option<InGameTime> {
+        let mut times = self
+            .events
+            .iter()
+            .filter(|(event_kind, _)| *event_kind == kind)
+            .map(|(_, time)| *time);
+        if forward {
+            times.find(|time| current_time < *time)
+        } else {
+            times.rfind(|time| *time < current_time)
+        }
+    }
+}
+
+/// A piano-roll of the replay's inputs, showing for every button when it was held.
+struct InputTimeline {
+    // For every button, the sorted intervals during which it was held.
+    held_intervals: Vec<Vec<(InGameTime, InGameTime)>>,
+}
+
+impl InputTimeline {
+    const W_LABEL: usize = 12;
+    const COLUMN_DURATION: Duration = Duration::from_millis(20);
+    const HEIGHT: u16 = 1 + Button::VARIANTS.len() as u16;
+
+    fn new(input_history: &UncompressedInputHistory, replay_length: InGameTime) -> Self {
+        let mut held_intervals = vec![Vec::new(); Button::VARIANTS.len()];
+        let mut held_since = [None; Button::VARIANTS.len()];
+
+        for (time, input) in input_history {
+            match input {
+                Input::Activate(button) => {
+                    held_since[*button as usize].get_or_insert(*time);
+                }
+                Input::Deactivate(button) => {
+                    if let Some(start) = held_since[*button as usize].take() {
+                        held_intervals[*button as usize].push((start, *time));
+                    }
+                }
+            }
+        }
+        // Buttons never released are held until the end.
+        for (intervals, start) in held_intervals.iter_mut().zip(held_since) {
+            if let Some(start) = start {
+                intervals.push((start, replay_length.max(start)));
+            }
+        }
+
+        Self { held_intervals }
+    }
+
+    fn is_held_during(&self, button: Button, t_0: InGameTime, t_1: InGameTime) -> bool {
+        let intervals = &self.held_intervals[button as usize];
+        let i = intervals.partition_point(|(start, _)| *start < t_1);
+        i > 0 && t_0 <= intervals[i - 1].1
+    }
+
+    fn render(&self, term: &mut impl Write, x: u16, y: u16, time: InGameTime) -> io::Result<()> {
+        let w_bars = usize::from(Application::<io::Stdout>::W_MAIN) - Self::W_LABEL;
+        // Show a bit of the upcoming inputs, but mostly the past ones.
+        let cursor_column = w_bars * 3 / 4;
+        let column_start = |column: usize| {
+            (time + Self::COLUMN_DURATION * u32::try_from(column).unwrap())
+                .saturating_sub(Self::COLUMN_DURATION * u32::try_from(cursor_column).unwrap())
+        };
+
+        term.queue(MoveTo(x, y))?.queue(Print(format!(
+            "{:<w_label$}{:>cursor_column$}{:w_rest$}",
+            fmt_duration(time),
+            "▼",
+            "",
+            w_label = Self::W_LABEL,
+            cursor_column = cursor_column + 1,
+            w_rest = w_bars - cursor_column - 1,
+        )))?;
+
+        for (i, button) in Button::VARIANTS.into_iter().enumerate() {
+            term.queue(MoveTo(x, y + 1 + u16::try_from(i).unwrap()))?
+                .queue(Print(format!(
+                    "{:<w_label$}",
+                    format!("{button:?}"),
+                    w_label = Self::W_LABEL
+                )))?;
+            for column in 0..w_bars {
+                let is_held =
+                    self.is_held_during(button, column_start(column), column_start(column + 1));
+                let cell = if is_held { "█" } else { "·" };
+                let cell = if column == cursor_column {
+                    cell.reverse()
+                } else if column > cursor_column {
+                    cell.dark_grey()
+                } else {
+                    cell.reset()
+                };
+                term.queue(PrintStyledContent(cell))?;
+            }
+        }
+
+        term.flush()
+    }
+}
+
+/// Where to place the main screen, and the input timeline below it if shown.
+fn replay_layout(show_input_timeline: bool) -> (u16, u16, u16) {
+    let (x_main, y_main) = Application::<io::Stdout>::fetch_main_xy();
+    if !show_input_timeline {
+        return (x_main, y_main, y_main);
+    }
+    // The Elektronika glyphset uses one row more than the others.
+    let h_main = Application::<io::Stdout>::H_MAIN + 1;
+    let (_, h_console) = terminal::size().unwrap_or((0, 0));
+    let y_main = h_console.saturating_sub(h_main + InputTimeline::HEIGHT) / 2;
+    (x_main, y_main, y_main + h_main)
+}
+
+/// Interleave the inputs done by intervention with the replay inputs loaded so far.
+///
+/// Each intervention input is stored together with the number of replay inputs that had been loaded when it was done.
+fn merge_intervention_inputs(
+    input_history: &UncompressedInputHistory,
     inputs_loaded: usize,
+    intervention_inputs: &[(usize, InGameTime, Input)],
+) -> UncompressedInputHistory {
+    let mut merged_input_history = UncompressedInputHistory::new();
+    let mut intervention_inputs = intervention_inputs.iter().peekable();
+
+    for (i, replay_input) in input_history.iter().take(inputs_loaded).enumerate() {
+        while let Some((_, time, input)) =
+            intervention_inputs.next_if(|(inputs_loaded_then, _, _)| *inputs_loaded_then <= i)
+        {
+            merged_input_history.push((*time, *input));
+        }
+        merged_input_history.push(*replay_input);
+    }
+    merged_input_history.extend(intervention_inputs.map(|(_, time, input)| (*time, *input)));
+
+    merged_input_history
 }
 
 impl<T: Write> Application<T> {
@@ -46,7 +257,7 @@ impl<T: Write> Application<T> {
               + Use player input to update game.
               + If budget ran out, break loop.
             - Set 'latest refresh' variable to ::now().
-            - Do game.update().
+            - Do Sandbox::update_game(&mut game, ).
               ** Note that in-game time at time of update can be determined with either
                  -- `duration elapsed IRL - duration paused`,
                  -- `in-game time before entering loop + in-game time elapsed since loop entered`.
@@ -92,6 +303,9 @@ impl<T: Write> Application<T> {
         // This toggle enables users to actually do inputs on the game.
         let mut enable_game_intervention_inputs = false;
 
+        // Inputs done by intervention since the last jump, so a game taken over from here can be replayed faithfully.
+        let mut intervention_inputs: Vec<(usize, InGameTime, Input)> = Vec::new();
+
         /*
         FIXME: This is a workaround for floating point imprecision.
            Originally we had `let replay_speed = 1.0f64;` but then we had issues such as:
@@ -114,21 +328,31 @@ impl<T: Write> Application<T> {
 
         // Initialized/load game and generate game_save_anchors if possible.
         const ANCHOR_INTERVAL: Duration = Duration::from_millis(1000);
-        let (mut game, game_save_anchors) = self.calculate_game_save_anchors(
+        let (mut game, game_save_anchors, replay_index) = self.calculate_game_save_anchors(
             game_restoration_data,
             ANCHOR_INTERVAL,
             replay_length,
         )?;
 
+        // Seeking to pieces, line clears and events.
+        let mut seek_prompt: Option<(SeekPrompt, String)> = None;
+        let mut seek_event_kind = ReplayEventKind::Tetra;
+
         let mut inputs_loaded = 0usize;
 
         // FPS counter.
         let mut renders_per_second_counter = 0u32;
         let mut renders_per_second_counter_start_time = Instant::now();
 
+        // Input timeline, shown below the game if there is room.
+        let input_timeline =
+            InputTimeline::new(&game_restoration_data.input_history, replay_length);
+        let mut show_input_timeline = terminal::size()
+            .is_ok_and(|(_, h_console)| Self::H_MAIN + 1 + InputTimeline::HEIGHT <= h_console);
+
         // Initial render.
 
-        let (x_main, y_main) = Application::<T>::fetch_main_xy();
+        let (mut x_main, mut y_main, mut y_timeline) = replay_layout(show_input_timeline);
         game_renderer.set_render_offset(usize::from(x_main), usize::from(y_main));
         game_renderer.reset_view_diff_state();
         game_renderer.render(
@@ -140,6 +364,9 @@ impl<T: Write> Application<T> {
             &keybinds_legend,
             Some((replay_length, calc_speed(replay_speed_stepper))),
         )?;
+        if show_input_timeline {
+            input_timeline.render(&mut self.term, x_main, y_timeline, game.state().time)?;
+        }
 
         // The 'real-life' time at which we enter the game loop.
         let time_game_loop_entered = Instant::now();
@@ -167,6 +394,8 @@ impl<T: Write> Application<T> {
 
             let mut jump_to_anchor: Option<usize> = None;
 
+            let mut jump_to_time: Option<InGameTime> = None;
+
             // Calculate the time of the next render we can catch.
             // We actually completely base this off the start of the session,
             // and just skip a render if we miss the window.
@@ -199,13 +428,13 @@ impl<T: Write> Application<T> {
                                         }
 
                                         match (code, modifiers) {
-                                            // [Esc]: Stop.
+                                            // [Esc]: Stop (unless typing into the seek prompt).
                                             (
                                                 KeyCode::Esc
                                                 | KeyCode::Char('q' | 'Q')
                                                 | KeyCode::Backspace,
                                                 _,
-                                            ) => {
+                                            ) if seek_prompt.is_none() => {
                                                 break 'update_and_render MenuUpdate::Pop;
                                             }
 
@@ -216,6 +445,230 @@ impl<T: Write> Application<T> {
                                                 );
                                             }
 
+                                            // Seek prompt open: type a number and confirm with [Enter].
+                                            (code, _) if seek_prompt.is_some() => {
+                                                let (prompt, digits) =
+                                                    seek_prompt.as_mut().unwrap();
+
+                                                match code {
+                                                    KeyCode::Char(c @ '0'..='9') => digits.push(c),
+
+                                                    KeyCode::Backspace => {
+                                                        digits.pop();
+                                                    }
+
+                                                    KeyCode::Enter => {
+                                                        let n = digits.parse::<u32>().unwrap_or(0);
+                                                        let (target_time, what) = match prompt {
+                                                            SeekPrompt::Piece => (
+                                                                replay_index.time_of_piece(
+                                                                    usize::try_from(n).unwrap(),
+                                                                ),
+                                                                format!("piece #{n}"),
+                                                            ),
+                                                            SeekPrompt::LineClear => (
+                                                                replay_index.time_after_lines(n),
+                                                                format!("{n} lines"),
+                                                            ),
+                                                        };
+
+                                                        if target_time.is_some() {
+                                                            jump_to_time = target_time;
+                                                        } else {
+                                                            game_renderer
+                                                                .push_game_notification_feed([(
+                                                                    Notification::Custom(format!(
+                                                                        "(Never reached {what})"
+                                                                    )),
+                                                                    game.state().time,
+                                                                )]);
+                                                        }
+
+                                                        seek_prompt = None;
+                                                    }
+
+                                                    // [Esc] or any other key cancels.
+                                                    _ => seek_prompt = None,
+                                                }
+
+                                                self.term
+                                                    .execute(MoveTo(0, 0))?
+                                                    .execute(Clear(ClearType::CurrentLine))?;
+                                                if let Some((prompt, digits)) = &seek_prompt {
+                                                    let what = match prompt {
+                                                        SeekPrompt::Piece => "Go to piece #",
+                                                        SeekPrompt::LineClear => {
+                                                            "Go to lines cleared: "
+                                                        }
+                                                    };
+                                                    self.term.execute(PrintStyledContent(
+                                                        Stylize::italic(format!("{what}{digits}_")),
+                                                    ))?;
+                                                }
+
+                                                if jump_to_time.is_some() {
+                                                    break 'wait;
+                                                }
+                                            }
+
+                                            // [P]: Open prompt to seek to piece number.
+                                            // [C]: Open prompt to seek to number of lines cleared.
+                                            (KeyCode::Char(c @ ('p' | 'P' | 'c' | 'C')), _)
+                                                if !enable_game_intervention_inputs =>
+                                            {
+                                                let (prompt, what) = if matches!(c, 'p' | 'P') {
+                                                    (SeekPrompt::Piece, "Go to piece #")
+                                                } else {
+                                                    (SeekPrompt::LineClear, "Go to lines cleared: ")
+                                                };
+
+                                                seek_prompt = Some((prompt, String::new()));
+
+                                                self.term
+                                                    .execute(MoveTo(0, 0))?
+                                                    .execute(Clear(ClearType::CurrentLine))?
+                                                    .execute(PrintStyledContent(
+                                                        Stylize::italic(format!("{what}_")),
+                                                    ))?;
+                                            }
+
+                                            // [Ctrl+F]: Export board as fumen.
+                                            (KeyCode::Char('f' | 'F'), KeyModifiers::CONTROL) => {
+                                                let text = match Self::export_board_fumen(
+                                                    &game,
+                                                    game_meta_data,
+                                                ) {
+                                                    Ok(path) => format!(
+                                                        "(Fumen exported to {})",
+                                                        path.display()
+                                                    ),
+                                                    Err(e) => format!("(Fumen export failed: {e})"),
+                                                };
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(text),
+                                                    game.state().time,
+                                                )]);
+
+                                                if paused {
+                                                    next_paused_with_extra_render_request =
+                                                        Some(true);
+                                                    break 'wait;
+                                                }
+                                            }
+
+                                            // [F]: Change which kind of event to seek.
+                                            (KeyCode::Char('f' | 'F'), _)
+                                                if !enable_game_intervention_inputs =>
+                                            {
+                                                let i = ReplayEventKind::VARIANTS
+                                                    .iter()
+                                                    .position(|kind| *kind == seek_event_kind)
+                                                    .unwrap();
+                                                seek_event_kind = ReplayEventKind::VARIANTS
+                                                    [(i + 1) % ReplayEventKind::VARIANTS.len()];
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(format!(
+                                                        "(Seek events: {seek_event_kind:?})"
+                                                    )),
+                                                    game.state().time,
+                                                )]);
+
+                                                if paused {
+                                                    next_paused_with_extra_render_request =
+                                                        Some(true);
+                                                    break 'wait;
+                                                }
+                                            }
+
+                                            // [[][]]: Seek to previous/next event.
+                                            (KeyCode::Char(c @ ('[' | ']')), _)
+                                                if !enable_game_intervention_inputs =>
+                                            {
+                                                let target_time = replay_index.time_of_event(
+                                                    seek_event_kind,
+                                                    game.state().time,
+                                                    c == ']',
+                                                );
+
+                                                if target_time.is_some() {
+                                                    jump_to_time = target_time;
+                                                } else {
+                                                    game_renderer.push_game_notification_feed([(
+                                                        Notification::Custom(format!(
+                                                            "(No more {seek_event_kind:?} events)"
+                                                        )),
+                                                        game.state().time,
+                                                    )]);
+                                                    if paused {
+                                                        next_paused_with_extra_render_request =
+                                                            Some(true);
+                                                    }
+                                                }
+
+                                                break 'wait;
+                                            }
+
+                                            // [T]: Toggle input timeline.
+                                            (KeyCode::Char('t' | 'T'), _)
+                                                if !enable_game_intervention_inputs =>
+                                            {
+                                                show_input_timeline ^= true;
+
+                                                (x_main, y_main, y_timeline) =
+                                                    replay_layout(show_input_timeline);
+                                                self.term.execute(Clear(ClearType::All))?;
+                                                game_renderer.set_render_offset(
+                                                    usize::from(x_main),
+                                                    usize::from(y_main),
+                                                );
+                                                game_renderer.reset_view_diff_state();
+
+                                                if paused {
+                                                    next_paused_with_extra_render_request =
+                                                        Some(true);
+                                                }
+
+                                                break 'wait;
+                                            }
+
+                                            // [A]: Look for a perfect clear with the pieces visible.
+                                            (KeyCode::Char('a' | 'A'), _)
+                                                if !enable_game_intervention_inputs =>
+                                            {
+                                                let text = match solver::find_perfect_clear(&game) {
+                                                    Some(solution) => {
+                                                        let text = format!(
+                                                            "(Perfect clear in {} pieces)",
+                                                            solution.len()
+                                                        );
+                                                        game_renderer.set_ghost_tiles(
+                                                            solver::placement_tiles(
+                                                                &game.state().board,
+                                                                &solution,
+                                                            ),
+                                                        );
+                                                        text
+                                                    }
+                                                    None => {
+                                                        "(No perfect clear with visible pieces)"
+                                                            .to_owned()
+                                                    }
+                                                };
+
+                                                game_renderer.push_game_notification_feed([(
+                                                    Notification::Custom(text),
+                                                    game.state().time,
+                                                )]);
+
+                                                if paused {
+                                                    next_paused_with_extra_render_request =
+                                                        Some(true);
+                                                    break 'wait;
+                                                }
+                                            }
+
                                             // [Ctrl+I]: Enable Interactive Instant-Input Intervention.
                                             (KeyCode::Char('i' | 'I'), KeyModifiers::CONTROL) => {
                                                 enable_game_intervention_inputs ^= true;
@@ -247,31 +700,39 @@ impl<T: Write> Application<T> {
 
                                                     // Binding found: Usebutton un-/press.
                                                     Some(&button) => {
-                                                        match game.update(
-                                                            game.state().time,
-                                                            Some(Input::Activate(button)),
-                                                        ) {
-                                                            Ok(msgs) => game_renderer
-                                                                .push_game_notification_feed(msgs),
-                                                            // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
-                                                            Err(
-                                                                UpdateGameError::TargetTimeInPast,
-                                                            ) => {}
-                                                            // Game ended.
-                                                            Err(UpdateGameError::AlreadyEnded) => {}
-                                                        }
-                                                        match game.update(
-                                                            game.state().time,
-                                                            Some(Input::Deactivate(button)),
-                                                        ) {
-                                                            Ok(msgs) => game_renderer
-                                                                .push_game_notification_feed(msgs),
-                                                            // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
-                                                            Err(
-                                                                UpdateGameError::TargetTimeInPast,
-                                                            ) => {}
-                                                            // Game ended.
-                                                            Err(UpdateGameError::AlreadyEnded) => {}
+                                                        // Round up to milliseconds, like player inputs in live games.
+                                                        let nanos = game.state().time.as_nanos();
+                                                        const NANOS_PER_MILLI: u128 = 1_000_000;
+                                                        let input_time = InGameTime::from_millis(
+                                                            nanos.div_ceil(NANOS_PER_MILLI) as u64,
+                                                        );
+
+                                                        for button_change in [
+                                                            Input::Activate(button),
+                                                            Input::Deactivate(button),
+                                                        ] {
+                                                            match Sandbox::update_game(&mut game,
+                                                                input_time,
+                                                                Some(button_change),
+                                                            ) {
+                                                                Ok(msgs) => {
+                                                                    game_renderer
+                                                                        .push_game_notification_feed(
+                                                                            msgs,
+                                                                        );
+                                                                    intervention_inputs.push((
+                                                                        inputs_loaded,
+                                                                        input_time,
+                                                                        button_change,
+                                                                    ));
+                                                                }
+                                                                // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
+                                                                Err(
+                                                                    UpdateGameError::TargetTimeInPast,
+                                                                ) => {}
+                                                                // Game ended.
+                                                                Err(UpdateGameError::AlreadyEnded) => {}
+                                                            }
                                                         }
                                                     }
                                                 }
@@ -283,34 +744,17 @@ impl<T: Write> Application<T> {
 
                                             // [Ctrl+S]: Store savepoint.
                                             (KeyCode::Char('s' | 'S'), KeyModifiers::CONTROL) => {
-                                                self.game_saves = (
-                                                    0,
-                                                    vec![GameSave {
-                                                        game_meta_data: game_meta_data.clone(),
-                                                        game_restoration_data:
-                                                            GameRestorationData::new(
-                                                                &game,
-                                                                game_restoration_data
-                                                                    .input_history
-                                                                    .clone(),
-                                                                matches!(
-                                                                    game.phase(),
-                                                                    Phase::GameEnd {
-                                                                        cause:
-                                                                            GameEndCause::Forfeit { .. },
-                                                                        ..
-                                                                    }
-                                                                )
-                                                                .then_some(game.state().time),
-                                                            ),
-                                                        inputs_to_load: inputs_loaded,
-                                                    }],
+                                                let name = self.store_game_save(
+                                                    game_meta_data,
+                                                    &game,
+                                                    game_restoration_data.input_history.clone(),
+                                                    inputs_loaded,
                                                 );
 
                                                 game_renderer.push_game_notification_feed([(
-                                                    Notification::Custom(
-                                                        "(Stored savepoint)".to_owned(),
-                                                    ),
+                                                    Notification::Custom(format!(
+                                                        "(Stored savepoint '{name}')"
+                                                    )),
                                                     game.state().time,
                                                 )]);
 
@@ -423,8 +867,11 @@ impl<T: Write> Application<T> {
                                                         }
                                                     }
 
-                                                    match game.update(update_target_time, opt_input)
-                                                    {
+                                                    match Sandbox::update_game(
+                                                        &mut game,
+                                                        update_target_time,
+                                                        opt_input,
+                                                    ) {
                                                         Ok(msgs) => game_renderer
                                                             .push_game_notification_feed(msgs),
                                                         // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
@@ -461,7 +908,8 @@ impl<T: Write> Application<T> {
                                                 {
                                                     // FIXME: We do not handle degenerate cases where input is available even tho game should forfeit.
 
-                                                    match game.update(
+                                                    match Sandbox::update_game(
+                                                        &mut game,
                                                         *next_input_time,
                                                         Some(*button_change),
                                                     ) {
@@ -535,6 +983,17 @@ impl<T: Write> Application<T> {
 
                                                 let mut the_meta_data = game_meta_data.clone();
                                                 the_meta_data.title.push('\'');
+                                                the_meta_data.datetime = chrono::Utc::now()
+                                                    .format("%Y-%m-%d_%H:%M")
+                                                    .to_string();
+                                                the_meta_data.official_daily = false;
+                                                the_meta_data.forked_from = Some(ReplayFork {
+                                                    parent_datetime: game_meta_data
+                                                        .datetime
+                                                        .clone(),
+                                                    parent_title: game_meta_data.title.clone(),
+                                                    fork_time: the_game.state().time,
+                                                });
 
                                                 // FIXME: Clone renderer when entering live game from here?
                                                 let the_game_renderer =
@@ -548,14 +1007,16 @@ impl<T: Write> Application<T> {
                                                 break 'update_and_render MenuUpdate::Push(
                                                     Menu::PlayGame {
                                                         game: Box::new(the_game),
-                                                        game_input_history: game_restoration_data
-                                                            .input_history
-                                                            .iter()
-                                                            .take(inputs_loaded)
-                                                            .copied()
-                                                            .collect(),
+                                                        game_input_history:
+                                                            merge_intervention_inputs(
+                                                                &game_restoration_data
+                                                                    .input_history,
+                                                                inputs_loaded,
+                                                                &intervention_inputs,
+                                                            ),
                                                         game_meta_data: the_meta_data,
                                                         game_renderer: Box::new(the_game_renderer),
+                                                        split_recorder: None,
                                                     },
                                                 );
                                             }
@@ -571,7 +1032,8 @@ impl<T: Write> Application<T> {
                                     event::Event::FocusLost => {}
                                     event::Event::Resize(_, _) => {
                                         // Need to redraw screen for proper centering etc.
-                                        let (x_main, y_main) = Application::<T>::fetch_main_xy();
+                                        (x_main, y_main, y_timeline) =
+                                            replay_layout(show_input_timeline);
                                         game_renderer.set_render_offset(
                                             usize::from(x_main),
                                             usize::from(y_main),
@@ -610,7 +1072,36 @@ impl<T: Write> Application<T> {
 
             let now = Instant::now();
 
-            if let Some(anchor_index) = jump_to_anchor.take() {
+            if let Some(target_time) = jump_to_time.take() {
+                // Remember: We convene on logically setting the 'refresh point' to before the update and render happens.
+                time_last_refresh = now;
+                intervention_inputs.clear();
+
+                (game, inputs_loaded) =
+                    game_restoration_data.seek(game_save_anchors.as_deref(), target_time);
+
+                // Reset renderer's state associated with game (since we could be at any other game state now).
+                game_renderer.reset_game_associated_state();
+
+                // Re-render full state.
+                game_renderer.render(
+                    &mut self.term,
+                    &game,
+                    game_meta_data,
+                    &self.settings,
+                    &self.temp_data,
+                    &keybinds_legend,
+                    Some((replay_length, calc_speed(replay_speed_stepper))),
+                )?;
+                if show_input_timeline {
+                    input_timeline.render(&mut self.term, x_main, y_timeline, game.state().time)?;
+                }
+
+                renders_per_second_counter += 1;
+
+                // Restart update-render loop as if we just entered it.
+                continue 'update_and_render;
+            } else if let Some(anchor_index) = jump_to_anchor.take() {
                 // We don't allow skipping beyond last anchor.
                 if anchor_index
                     > ((replay_length.as_secs_f64() / ANCHOR_INTERVAL.as_secs_f64()).floor()
@@ -621,6 +1112,7 @@ impl<T: Write> Application<T> {
 
                 // Remember: We convene on logically setting the 'refresh point' to before the update and render happens.
                 time_last_refresh = now;
+                intervention_inputs.clear();
 
                 // Actually jump to position.
                 if let Some(game_save_anchors) = &game_save_anchors {
@@ -643,7 +1135,7 @@ impl<T: Write> Application<T> {
                         Ok(idx) | Err(idx) => idx,
                     };
                     game = game_restoration_data.restore(idx);
-                    match game.update(tgt_time, None) {
+                    match Sandbox::update_game(&mut game, tgt_time, None) {
                         Ok(msgs) => game_renderer.push_game_notification_feed(msgs),
                         // FIXME: Handle UpdateGameError? If not, why not?
                         Err(_e) => {}
@@ -664,6 +1156,9 @@ impl<T: Write> Application<T> {
                     &keybinds_legend,
                     Some((replay_length, calc_speed(replay_speed_stepper))),
                 )?;
+                if show_input_timeline {
+                    input_timeline.render(&mut self.term, x_main, y_timeline, game.state().time)?;
+                }
 
                 renders_per_second_counter += 1;
 
@@ -701,7 +1196,7 @@ impl<T: Write> Application<T> {
                         break 'feed_inputs;
                     }
 
-                    match game.update(*next_input_time, Some(*button_change)) {
+                    match Sandbox::update_game(&mut game, *next_input_time, Some(*button_change)) {
                         Ok(msgs) => game_renderer.push_game_notification_feed(msgs),
                         // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
                         Err(UpdateGameError::TargetTimeInPast) => {}
@@ -712,7 +1207,7 @@ impl<T: Write> Application<T> {
                     inputs_loaded += 1;
                 }
 
-                match game.update(update_target_time, None) {
+                match Sandbox::update_game(&mut game, update_target_time, None) {
                     // Update.
                     Ok(msgs) => game_renderer.push_game_notification_feed(msgs),
 
@@ -743,6 +1238,9 @@ impl<T: Write> Application<T> {
                     &keybinds_legend,
                     Some((replay_length, calc_speed(replay_speed_stepper))),
                 )?;
+                if show_input_timeline {
+                    input_timeline.render(&mut self.term, x_main, y_timeline, game.state().time)?;
+                }
 
                 renders_per_second_counter += 1;
             }
@@ -758,8 +1256,7 @@ impl<T: Write> Application<T> {
             // Remove 'paused' message.
             } else if paused && next_paused_with_extra_render_request.is_none() {
                 self.term.execute(MoveTo(0, 0))?;
-                self.term
-                    .execute(Clear(crossterm::terminal::ClearType::CurrentLine))?;
+                self.term.execute(Clear(ClearType::CurrentLine))?;
 
             // Render FPS counter.
             } else if next_paused_with_extra_render_request.is_none()
@@ -823,25 +1320,27 @@ impl<T: Write> Application<T> {
 
     // FIXME: Add better error detection for fail cases, probably use renderer feed.
     // FIXME: We do not treat degenerate games that end immediately (total time = 0).
-    fn calculate_game_save_anchors(
+    pub(in crate::application) fn calculate_game_save_anchors(
         &mut self,
         game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
         anchor_interval: Duration,
         replay_length: InGameTime,
-    ) -> io::Result<(Game, Option<Vec<GameSaveAnchor>>)> {
+    ) -> io::Result<(Game, Option<Vec<GameSaveAnchor>>, ReplayIndex)> {
         let initial_game = game_restoration_data.restore(0);
 
-        let mut game = match initial_game.try_clone() {
-            Ok(game) => game,
-            // We don't have replay anchors for modded games, because we can't even attempt to clone the mods' internal states at time of writing.
-            Err(_e) => return Ok((initial_game, None)),
-        };
-        let mut inputs_loaded = 0usize;
+        let mut replay_index = ReplayIndex::default();
 
-        let mut game_save_anchors = vec![GameSaveAnchor {
-            game: game.try_clone().unwrap(),
-            inputs_loaded,
-        }];
+        // We don't have replay anchors for modded games, because we can't even attempt to clone the mods' internal states at time of writing.
+        // We still go through the game to index it, though.
+        let mut game_save_anchors = initial_game.try_clone().ok().map(|game| {
+            vec![GameSaveAnchor {
+                game,
+                inputs_loaded: 0,
+            }]
+        });
+
+        let mut game = game_restoration_data.restore(0);
+        let mut inputs_loaded = 0usize;
 
         let mut next_anchor_time = game.state().time + anchor_interval;
 
@@ -868,14 +1367,25 @@ impl<T: Write> Application<T> {
                     break 'feed_inputs;
                 }
 
-                match game.update(*next_input_time, Some(*button_change)) {
-                    Ok(_msgs) => {}
+                let piece_held_before = game.state().piece_held;
+
+                match Sandbox::update_game(&mut game, *next_input_time, Some(*button_change)) {
+                    Ok(msgs) => replay_index.index_feed(&msgs),
                     // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
                     Err(UpdateGameError::TargetTimeInPast) => {}
                     // Game ended, no more anchors.
                     Err(UpdateGameError::AlreadyEnded) => break 'calculate_anchors,
                 }
 
+                // Holds do not cause a notification, so we detect them by hand.
+                if *button_change == Input::Activate(Button::HoldPiece)
+                    && game.state().piece_held != piece_held_before
+                {
+                    replay_index
+                        .events
+                        .push((ReplayEventKind::Hold, *next_input_time));
+                }
+
                 inputs_loaded += 1;
             }
 
@@ -887,18 +1397,20 @@ impl<T: Write> Application<T> {
             }
 
             // Anchor is next.
-            match game.update(next_anchor_time, None) {
-                Ok(_msgs) => {}
+            match Sandbox::update_game(&mut game, next_anchor_time, None) {
+                Ok(msgs) => replay_index.index_feed(&msgs),
                 // FIXME: Handle UpdateGameError::TargetTimeInPast? If not, why not?
                 Err(UpdateGameError::TargetTimeInPast) => {}
                 // Game ended, no more anchors.
                 Err(UpdateGameError::AlreadyEnded) => break 'calculate_anchors,
             }
 
-            game_save_anchors.push(GameSaveAnchor {
-                game: game.try_clone().unwrap(),
-                inputs_loaded,
-            });
+            if let Some(game_save_anchors) = &mut game_save_anchors {
+                game_save_anchors.push(GameSaveAnchor {
+                    game: game.try_clone().unwrap(),
+                    inputs_loaded,
+                });
+            }
 
             next_anchor_time = game.state().time + anchor_interval;
 
@@ -908,7 +1420,7 @@ impl<T: Write> Application<T> {
             }
         }
 
-        Ok((initial_game, Some(game_save_anchors)))
+        Ok((initial_game, game_save_anchors, replay_index))
     }
 }
 
@@ -0,0 +1,323 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor::MoveTo,
+    event::{
+        self, Event, KeyCode, KeyEvent,
+        KeyEventKind::{Press, Repeat},
+        KeyModifiers,
+    },
+    style::{Color, Print, PrintStyledContent, StyledContent, Stylize},
+    terminal::{Clear, ClearType},
+    QueueableCommand,
+};
+use falling_tetromino_engine::{Game, InGameTime, Phase};
+
+use crate::{
+    application::{
+        menus::{Menu, MenuUpdate},
+        Application, GameSave, Settings,
+    },
+    fmt_helpers::fmt_duration,
+    game_renderers::TetroTUIRenderer,
+};
+
+pub(in crate::application) type Thumbnail = Vec<Vec<StyledContent<&'static str>>>;
+
+/// Draw a small picture of the visible board (including the piece in play) with two board rows per text line.
+pub(in crate::application) fn board_thumbnail(game: &Game, settings: &Settings) -> Thumbnail {
+    let mut colors = [[None; Game::WIDTH]; Game::LOCK_OUT_HEIGHT];
+    // Tiles without palette color are drawn in the default color.
+    let default_color = Color::Reset;
+
+    for (y, line) in game
+        .state()
+        .board
+        .iter()
+        .take(Game::LOCK_OUT_HEIGHT)
+        .enumerate()
+    {
+        for (x, tile) in line.iter().enumerate() {
+            if let Some(tile_id) = tile {
+                colors[y][x] = Some(
+                    settings
+                        .palette_lockedtiles()
+                        .get(tile_id)
+                        .copied()
+                        .unwrap_or(default_color),
+                );
+            }
+        }
+    }
+    if let Phase::PieceInPlay { piece, .. } = game.phase() {
+        for ((x, y), tile_id) in piece.tiles() {
+            if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                if y < Game::LOCK_OUT_HEIGHT {
+                    colors[y][x] = Some(
+                        settings
+                            .palette()
+                            .get(&tile_id)
+                            .copied()
+                            .unwrap_or(default_color),
+                    );
+                }
+            }
+        }
+    }
+
+    (0..Game::LOCK_OUT_HEIGHT / 2)
+        .rev()
+        .map(|y| {
+            (0..Game::WIDTH)
+                .map(|x| match (colors[2 * y + 1][x], colors[2 * y][x]) {
+                    (Some(top), Some(bottom)) if top == bottom => "█".with(top),
+                    (Some(top), Some(bottom)) => "▀".with(top).on(bottom),
+                    (Some(top), None) => "▀".with(top),
+                    (None, Some(bottom)) => "▄".with(bottom),
+                    (None, None) => " ".reset(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl<T: Write> Application<T> {
+    pub(in crate::application) fn run_menu_savepoints(&mut self) -> io::Result<MenuUpdate> {
+        const CAMERA_SIZE: usize = 11;
+        const W_LIST: usize = 46;
+
+        let mut selected = self.game_saves.0;
+        let mut camera_pos = 0usize;
+        // Name being typed when renaming the selected savepoint.
+        let mut renaming: Option<String> = None;
+        // Thumbnail and time of the selected savepoint, only recalculated when selection changes.
+        let mut thumbnail: Option<(usize, Thumbnail, InGameTime)> = None;
+
+        loop {
+            let w_main = Self::W_MAIN.into();
+            let (x_main, y_main) = Self::fetch_main_xy();
+            let y_selection = Self::H_MAIN / 5;
+            let n_saves = self.game_saves.1.len();
+
+            if n_saves > 0 {
+                selected = selected.min(n_saves - 1);
+                camera_pos = camera_pos
+                    .min(selected)
+                    .max((selected + 1).saturating_sub(CAMERA_SIZE));
+            }
+
+            self.term
+                .queue(Clear(ClearType::All))?
+                .queue(MoveTo(x_main, y_main + y_selection))?
+                .queue(PrintStyledContent(
+                    format!("{:^w_main$}", "* Savepoints *").bold(),
+                ))?
+                .queue(MoveTo(x_main, y_main + y_selection + 2))?
+                .queue(Print(format!("{:^w_main$}", "──────────────────────────")))?;
+
+            if n_saves == 0 {
+                self.term
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 3))?
+                    .queue(PrintStyledContent(
+                        format!("{:^w_main$}", "There are no savepoints.").italic(),
+                    ))?
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 4))?
+                    .queue(PrintStyledContent(
+                        format!("{:^w_main$}", "Store some during a game with [Ctrl+S]!").italic(),
+                    ))?;
+            } else {
+                for (i, GameSave { name, .. }) in self
+                    .game_saves
+                    .1
+                    .iter()
+                    .enumerate()
+                    .skip(camera_pos)
+                    .take(CAMERA_SIZE)
+                {
+                    let entry = match &renaming {
+                        Some(new_name) if i == selected => format!(">{new_name}_"),
+                        _ if i == selected => format!(">{name}"),
+                        _ => format!(" {name}"),
+                    };
+                    let entry: String = entry.chars().take(W_LIST).collect();
+                    self.term
+                        .queue(MoveTo(
+                            x_main,
+                            y_main + y_selection + 4 + u16::try_from(i - camera_pos).unwrap(),
+                        ))?
+                        .queue(PrintStyledContent(if i == selected {
+                            format!("{entry:<W_LIST$}").bold()
+                        } else {
+                            format!("{entry:<W_LIST$}").reset()
+                        }))?;
+                }
+
+                // Thumbnail of selected savepoint.
+                if thumbnail.as_ref().is_none_or(|(i, _, _)| *i != selected) {
+                    let (_, game, _) = self.game_saves.1[selected].load();
+                    thumbnail = Some((
+                        selected,
+                        board_thumbnail(&game, &self.settings),
+                        game.state().time,
+                    ));
+                }
+                if let Some((_, lines, time)) = &thumbnail {
+                    let x_thumbnail = x_main + u16::try_from(W_LIST).unwrap() + 2;
+                    let y_thumbnail = y_main + y_selection + 4;
+                    for (dy, line) in lines.iter().enumerate() {
+                        self.term
+                            .queue(MoveTo(
+                                x_thumbnail,
+                                y_thumbnail + u16::try_from(dy).unwrap(),
+                            ))?
+                            .queue(Print("│"))?;
+                        for cell in line {
+                            self.term.queue(PrintStyledContent(*cell))?;
+                        }
+                        self.term.queue(Print("│"))?;
+                    }
+                    self.term
+                        .queue(MoveTo(
+                            x_thumbnail,
+                            y_thumbnail + u16::try_from(lines.len()).unwrap(),
+                        ))?
+                        .queue(PrintStyledContent(
+                            format!("{:^12}", fmt_duration(*time)).italic(),
+                        ))?;
+                }
+            }
+
+            self.term
+                .queue(MoveTo(
+                    x_main,
+                    y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap() + 2,
+                ))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        if renaming.is_some() {
+                            "(Renaming: [Enter]=confirm [Esc]=cancel)"
+                        } else {
+                            "(Controls: [↓|↑]=scroll [Enter]=load [Del]=delete [R]=rename)"
+                        }
+                    )
+                    .italic(),
+                ))?;
+            self.term.flush()?;
+
+            // Wait for new input.
+            let event = event::read()?;
+
+            // Renaming consumes all key presses.
+            if let Some(new_name) = &mut renaming {
+                if let Event::Key(KeyEvent {
+                    code,
+                    kind: Press | Repeat,
+                    ..
+                }) = event
+                {
+                    match code {
+                        KeyCode::Enter => {
+                            self.game_saves.1[selected].name = std::mem::take(new_name);
+                            renaming = None;
+                        }
+                        KeyCode::Esc => renaming = None,
+                        KeyCode::Backspace => {
+                            new_name.pop();
+                        }
+                        KeyCode::Char(c) => new_name.push(c),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+
+            match event {
+                // Quit menu.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c' | 'C'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: Press | Repeat,
+                    state: _,
+                }) => break Ok(MenuUpdate::Push(Menu::Quit)),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Esc | KeyCode::Char('q' | 'Q') | KeyCode::Backspace,
+                    kind: Press,
+                    ..
+                }) => break Ok(MenuUpdate::Pop),
+
+                // Move selector up.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up | KeyCode::Char('k' | 'K'),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_saves > 0 => {
+                    selected = (selected + n_saves - 1) % n_saves;
+                }
+
+                // Move selector down.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Down | KeyCode::Char('j' | 'J'),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_saves > 0 => {
+                    selected = (selected + 1) % n_saves;
+                }
+
+                // Delete savepoint.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Delete | KeyCode::Char('d' | 'D'),
+                    kind: Press,
+                    ..
+                }) if n_saves > 0 => {
+                    self.game_saves.1.remove(selected);
+                    if selected < self.game_saves.0 {
+                        self.game_saves.0 -= 1;
+                    }
+                    self.game_saves.0 = self
+                        .game_saves
+                        .0
+                        .min(self.game_saves.1.len().saturating_sub(1));
+                    thumbnail = None;
+                }
+
+                // Rename savepoint.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('r' | 'R'),
+                    kind: Press,
+                    ..
+                }) if n_saves > 0 => {
+                    renaming = Some(self.game_saves.1[selected].name.clone());
+                }
+
+                // Load savepoint.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Enter | KeyCode::Char('e' | 'E'),
+                    kind: Press,
+                    ..
+                }) if n_saves > 0 => {
+                    self.game_saves.0 = selected;
+
+                    let (game_meta_data, game, game_input_history) =
+                        self.game_saves.1[selected].load();
+
+                    let game_renderer =
+                        TetroTUIRenderer::with_number(self.temp_data.renderernumber);
+
+                    self.statistics.total_new_games += 1;
+
+                    break Ok(MenuUpdate::Push(Menu::PlayGame {
+                        game: Box::new(game),
+                        game_input_history,
+                        game_meta_data,
+                        game_renderer: Box::new(game_renderer),
+                        split_recorder: None,
+                    }));
+                }
+
+                // Other event: don't care.
+                _ => {}
+            }
+        }
+    }
+}
@@ -11,12 +11,13 @@ use crossterm::{
     terminal::{Clear, ClearType},
     QueueableCommand,
 };
-use falling_tetromino_engine::Stat;
+use falling_tetromino_engine::{InGameTime, Stat};
 
 use crate::{
     application::{
         menus::{Menu, MenuUpdate},
-        Application, CompressedInputHistory, GameRestorationData, ScoreEntry, ScoreEntrySorting,
+        Application, CompressedInputHistory, GameMetaData, GameRestorationData, ScoreEntry,
+        ScoreEntrySorting,
     },
     fmt_helpers::fmt_duration,
     game_renderers::TetroTUIRenderer,
@@ -32,6 +33,13 @@ impl<T: Write> Application<T> {
         let mut re_sort_scoreboard = true;
         const CAMERA_SIZE: usize = 11;
         const CAMERA_MARGIN: usize = 2;
+        let mut export_message: Option<String> = None;
+        // Replay marked to be compared with another one.
+        let mut compare_mark: Option<(
+            GameMetaData,
+            InGameTime,
+            GameRestorationData<CompressedInputHistory>,
+        )> = None;
         loop {
             let w_main = Self::W_MAIN.into();
             let (x_main, y_main) = Self::fetch_main_xy();
@@ -77,13 +85,39 @@ impl<T: Write> Application<T> {
             )| {
                 let lhs_annotation = match sorting {
                     ScoreEntrySorting::Chronological => entry.game_meta_data.datetime.to_owned(),
+                    // Games that used undo or tools are not ranked.
+                    ScoreEntrySorting::ModeDependent | ScoreEntrySorting::GameStat(_)
+                        if !entry.game_meta_data.is_ranked() =>
+                    {
+                        " --".to_owned()
+                    }
                     ScoreEntrySorting::ModeDependent | ScoreEntrySorting::GameStat(_) => {
                         format!("{rank: >2}{}", if rank == 1 { '#' } else { '.' })
                     }
                 };
                 format!(
-                    "{} {}{} | {}{}",
+                    "{} {}{}{}{}{}{} | {}{}",
                     lhs_annotation,
+                    if entry.game_meta_data.tool_assisted {
+                        "tas."
+                    } else {
+                        ""
+                    },
+                    if entry.game_meta_data.bot_played {
+                        "bot."
+                    } else {
+                        ""
+                    },
+                    if entry.game_meta_data.used_undo {
+                        "undo."
+                    } else {
+                        ""
+                    },
+                    if entry.game_meta_data.used_solver {
+                        "solv."
+                    } else {
+                        ""
+                    },
                     if entry.is_win { "" } else { "unf." },
                     entry.game_meta_data.title,
                     fmt_stat(entry),
@@ -203,7 +237,37 @@ impl<T: Write> Application<T> {
                 .queue(PrintStyledContent(
                     format!(
                         "{:^w_main$}",
-                        "(Controls: [↓|↑]=scroll [Del]=delete [Enter]=replay°)"
+                        "(Controls: [↓|↑]=scroll [Del]=delete [Enter]=replay° [T]=edit)"
+                    )
+                    .italic(),
+                ))?;
+            self.term
+                .queue(MoveTo(
+                    x_main,
+                    y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap() + 3,
+                ))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        export_message.take().unwrap_or_else(|| {
+                            match self
+                                .scores_and_replays
+                                .entries
+                                .get(*cursor_pos)
+                                .and_then(|(entry, _)| entry.game_meta_data.forked_from.as_ref())
+                            {
+                                Some(fork) => format!(
+                                    "(Forked from {} {} at {})",
+                                    fork.parent_title,
+                                    fork.parent_datetime,
+                                    fmt_duration(fork.fork_time)
+                                ),
+                                None => {
+                                    "(Replay°: [X]=asciicast [Alt+X]=svg [Ctrl+X]=text [C]=compare)"
+                                        .to_owned()
+                                }
+                            }
+                        })
                     )
                     .italic(),
                 ))?;
@@ -406,6 +470,141 @@ impl<T: Write> Application<T> {
                     }
                 }
 
+                // Export replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('x' | 'X'),
+                    kind: Press,
+                    modifiers,
+                    ..
+                }) if self.scores_and_replays.entries.len() > 0 => {
+                    if let (
+                        ScoreEntry {
+                            game_meta_data,
+                            time_elapsed,
+                            ..
+                        },
+                        Some(game_restoration_data),
+                    ) = &self.scores_and_replays.entries[*cursor_pos]
+                    {
+                        self.term
+                            .queue(MoveTo(
+                                x_main,
+                                y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap() + 3,
+                            ))?
+                            .queue(PrintStyledContent(
+                                format!("{:^w_main$}", "Exporting replay...").italic(),
+                            ))?;
+                        self.term.flush()?;
+
+                        let game_restoration_data = game_restoration_data
+                            .clone()
+                            .map(|input_history| input_history.decompress());
+
+                        let result = if modifiers.contains(KeyModifiers::CONTROL) {
+                            Self::export_replay_text(&game_restoration_data, game_meta_data)
+                        } else if modifiers.contains(KeyModifiers::ALT) {
+                            self.export_replay_svg(
+                                &game_restoration_data,
+                                game_meta_data,
+                                *time_elapsed,
+                            )
+                        } else {
+                            self.export_replay_asciicast(
+                                &game_restoration_data,
+                                game_meta_data,
+                                *time_elapsed,
+                            )
+                        };
+
+                        export_message = Some(match result {
+                            Ok(path) => format!("Exported to {}", path.display()),
+                            Err(e) => format!("Export failed: {e}"),
+                        });
+                    } else {
+                        export_message = Some("No replay available to export.".to_owned());
+                    }
+                }
+
+                // Edit replay inputs in the TAS editor.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('t' | 'T'),
+                    kind: Press,
+                    ..
+                }) if self.scores_and_replays.entries.len() > 0 => {
+                    if let (ScoreEntry { game_meta_data, .. }, Some(game_restoration_data)) =
+                        &self.scores_and_replays.entries[*cursor_pos]
+                    {
+                        let game_restoration_data = game_restoration_data
+                            .clone()
+                            .map(|input_history| input_history.decompress());
+
+                        break Ok(MenuUpdate::Push(Menu::TasEditor {
+                            game_restoration_data: Box::new(game_restoration_data),
+                            game_meta_data: game_meta_data.clone(),
+                            cursor_pos: 0,
+                            unsaved_edits: false,
+                        }));
+                    } else {
+                        export_message = Some("No replay available to edit.".to_owned());
+                    }
+                }
+
+                // Mark replay for comparison, or compare with marked replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c' | 'C'),
+                    kind: Press,
+                    ..
+                }) if self.scores_and_replays.entries.len() > 0 => {
+                    if let (
+                        ScoreEntry {
+                            game_meta_data,
+                            time_elapsed,
+                            ..
+                        },
+                        Some(game_restoration_data),
+                    ) = &self.scores_and_replays.entries[*cursor_pos]
+                    {
+                        let selected = (
+                            game_meta_data.clone(),
+                            *time_elapsed,
+                            game_restoration_data.clone(),
+                        );
+
+                        match compare_mark.take() {
+                            None => {
+                                compare_mark = Some(selected);
+                                export_message = Some(
+                                    "Marked for comparison, [C] on another replay°.".to_owned(),
+                                );
+                            }
+                            Some(marked) if marked == selected => {
+                                export_message = Some("Unmarked for comparison.".to_owned());
+                            }
+                            Some(marked) => {
+                                let decompress =
+                                    |(game_meta_data, replay_length, game_restoration_data): (
+                                        GameMetaData,
+                                        InGameTime,
+                                        GameRestorationData<CompressedInputHistory>,
+                                    )| {
+                                        (
+                                            game_restoration_data
+                                                .map(|input_history| input_history.decompress()),
+                                            game_meta_data,
+                                            replay_length,
+                                        )
+                                    };
+
+                                break Ok(MenuUpdate::Push(Menu::CompareReplays {
+                                    replays: Box::new([decompress(marked), decompress(selected)]),
+                                }));
+                            }
+                        }
+                    } else {
+                        export_message = Some("No replay available to compare.".to_owned());
+                    }
+                }
+
                 // Other event: don't care.
                 _ => {}
             };
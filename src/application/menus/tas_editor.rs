@@ -0,0 +1,563 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::MoveTo,
+    event::{
+        self, Event, KeyCode, KeyEvent,
+        KeyEventKind::{Press, Repeat},
+        KeyModifiers,
+    },
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{Clear, ClearType},
+    QueueableCommand,
+};
+use falling_tetromino_engine::{Button, Game, InGameTime, Input};
+
+use crate::{
+    application::{
+        menus::{savepoints::board_thumbnail, Menu, MenuUpdate},
+        Application, CompressedInputHistory, GameMetaData, GameRestorationData, GameSaveAnchor,
+        ScoreEntry, SplitMilestones, UncompressedInputHistory,
+    },
+    fmt_helpers::fmt_duration,
+    game_modes::game_modifiers::Sandbox,
+    game_renderers::TetroTUIRenderer,
+};
+
+/// How much in-game time lies between the games kept around to re-simulate edits from, like when replaying.
+const ANCHOR_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// The amounts of time to choose from for shifting inputs.
+const TIME_STEPS: [Duration; 4] = [
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_millis(1000),
+];
+
+/// Re-simulate the game up to (excluding) the given input, starting from the latest anchor before it and adding new anchors along the way.
+///
+/// Games whose mods cannot be cloned have no anchors and are re-simulated from the start every time.
+fn simulate(
+    game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+    game_save_anchors: &mut Vec<GameSaveAnchor>,
+    inputs_to_load: usize,
+) -> Game {
+    let latest_anchor = game_save_anchors
+        .iter()
+        .rfind(|anchor| anchor.inputs_loaded <= inputs_to_load);
+    let (mut game, mut inputs_loaded) = if let Some(GameSaveAnchor {
+        game: anchor_game,
+        inputs_loaded: anchor_inputs_loaded,
+    }) = latest_anchor
+    {
+        (anchor_game.try_clone().unwrap(), *anchor_inputs_loaded)
+    } else {
+        (game_restoration_data.restore(0), 0)
+    };
+
+    // The game is shown as right after the last input loaded.
+    let target_time = inputs_to_load
+        .checked_sub(1)
+        .map_or(game.state().time, |idx| {
+            game_restoration_data.input_history[idx].0
+        });
+
+    game_restoration_data.resimulate(
+        &mut game,
+        &mut inputs_loaded,
+        inputs_to_load,
+        target_time,
+        |game, input_idx, _msgs| {
+            let (Some(input_idx), Some(latest_anchor)) = (input_idx, game_save_anchors.last())
+            else {
+                return;
+            };
+            if latest_anchor.inputs_loaded <= input_idx
+                && latest_anchor.game.state().time + ANCHOR_INTERVAL <= game.state().time
+            {
+                if let Ok(anchor_game) = game.try_clone() {
+                    game_save_anchors.push(GameSaveAnchor {
+                        game: anchor_game,
+                        inputs_loaded: input_idx + 1,
+                    });
+                }
+            }
+        },
+    );
+
+    game
+}
+
+/// Re-simulate all inputs and end the game, by forfeit if it does not end by itself.
+///
+/// A game that was forfeit originally is run until the same time if the inputs end before.
+fn simulate_to_end(
+    game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+    game_save_anchors: &mut Vec<GameSaveAnchor>,
+) -> (Game, GameRestorationData<UncompressedInputHistory>) {
+    let mut game = simulate(
+        game_restoration_data,
+        game_save_anchors,
+        game_restoration_data.input_history.len(),
+    );
+
+    if let Some(forfeit_time) = game_restoration_data.forfeit {
+        let forfeit_time = forfeit_time.max(game.state().time);
+        let _v = Sandbox::update_game(&mut game, forfeit_time, None);
+    }
+
+    let forfeit = (!game.has_ended()).then(|| {
+        let _v = game.forfeit();
+        game.state().time
+    });
+
+    let mut game_restoration_data = game_restoration_data.clone();
+    game_restoration_data.forfeit = forfeit;
+
+    (game, game_restoration_data)
+}
+
+impl<T: Write> Application<T> {
+    pub(in crate::application) fn run_menu_tas_editor(
+        &mut self,
+        game_restoration_data: &mut GameRestorationData<UncompressedInputHistory>,
+        game_meta_data: &GameMetaData,
+        cursor_pos: &mut usize,
+        unsaved_edits: &mut bool,
+    ) -> io::Result<MenuUpdate> {
+        const CAMERA_SIZE: usize = 11;
+        const W_LIST: usize = 46;
+
+        // We don't have anchors for games whose mods cannot be cloned, like when replaying.
+        let mut game_save_anchors = game_restoration_data
+            .restore(0)
+            .try_clone()
+            .ok()
+            .map(|game| GameSaveAnchor {
+                game,
+                inputs_loaded: 0,
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let mut camera_pos = cursor_pos.saturating_sub(CAMERA_SIZE / 2);
+        let mut time_step_idx = 0usize;
+        let mut message: Option<String> = None;
+        // The re-simulated game after the selected input, only recalculated after moving or editing.
+        let mut selected_game: Option<(usize, Game)> = None;
+
+        loop {
+            let w_main = Self::W_MAIN.into();
+            let (x_main, y_main) = Self::fetch_main_xy();
+            let y_selection = Self::H_MAIN / 5;
+            let n_inputs = game_restoration_data.input_history.len();
+
+            *cursor_pos = (*cursor_pos).min(n_inputs.saturating_sub(1));
+            camera_pos = camera_pos
+                .min(*cursor_pos)
+                .max((*cursor_pos + 1).saturating_sub(CAMERA_SIZE));
+
+            self.term
+                .queue(Clear(ClearType::All))?
+                .queue(MoveTo(x_main, y_main + y_selection))?
+                .queue(PrintStyledContent(
+                    format!(
+                        "{:^w_main$}",
+                        format!("* TAS Editor ({}) *", game_meta_data.title)
+                    )
+                    .bold(),
+                ))?
+                .queue(MoveTo(x_main, y_main + y_selection + 2))?
+                .queue(Print(format!("{:^w_main$}", "──────────────────────────")))?;
+
+            if n_inputs == 0 {
+                self.term
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 3))?
+                    .queue(PrintStyledContent(
+                        format!("{:^W_LIST$}", "There are no inputs.").italic(),
+                    ))?
+                    .queue(MoveTo(x_main, y_main + y_selection + 4 + 4))?
+                    .queue(PrintStyledContent(
+                        format!("{:^W_LIST$}", "Add one with [Ins]!").italic(),
+                    ))?;
+            }
+
+            for (i, (update_time, input)) in game_restoration_data
+                .input_history
+                .iter()
+                .enumerate()
+                .skip(camera_pos)
+                .take(CAMERA_SIZE)
+            {
+                let (sign, button) = match input {
+                    Input::Activate(button) => ('+', button),
+                    Input::Deactivate(button) => ('-', button),
+                };
+                let entry = format!(
+                    "{}{i:>5} {:>9}ms {sign}{button:?}",
+                    if i == *cursor_pos { '>' } else { ' ' },
+                    update_time.as_millis(),
+                );
+                self.term
+                    .queue(MoveTo(
+                        x_main,
+                        y_main + y_selection + 4 + u16::try_from(i - camera_pos).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(if i == *cursor_pos {
+                        format!("{entry:<W_LIST$}").bold()
+                    } else {
+                        format!("{entry:<W_LIST$}").reset()
+                    }))?;
+            }
+
+            // Thumbnail of the game after the selected input.
+            let inputs_to_load = (*cursor_pos + 1).min(n_inputs);
+            if selected_game
+                .as_ref()
+                .is_none_or(|(i, _)| *i != inputs_to_load)
+            {
+                let game = simulate(
+                    game_restoration_data,
+                    &mut game_save_anchors,
+                    inputs_to_load,
+                );
+                selected_game = Some((inputs_to_load, game));
+            }
+            if let Some((_, game)) = &selected_game {
+                let lines = board_thumbnail(game, &self.settings);
+                let x_thumbnail = x_main + u16::try_from(W_LIST).unwrap() + 2;
+                let y_thumbnail = y_main + y_selection + 4;
+                for (dy, line) in lines.iter().enumerate() {
+                    self.term
+                        .queue(MoveTo(
+                            x_thumbnail,
+                            y_thumbnail + u16::try_from(dy).unwrap(),
+                        ))?
+                        .queue(Print("│"))?;
+                    for cell in line {
+                        self.term.queue(PrintStyledContent(*cell))?;
+                    }
+                    self.term.queue(Print("│"))?;
+                }
+                self.term
+                    .queue(MoveTo(
+                        x_thumbnail,
+                        y_thumbnail + u16::try_from(lines.len()).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(
+                        format!("{:^12}", fmt_duration(game.state().time)).italic(),
+                    ))?;
+
+                let state = game.state();
+                let status = format!(
+                    "{} lines, {} pieces, {} points{} | step {}ms",
+                    state.lineclears,
+                    state.pieces_locked.iter().sum::<u32>(),
+                    state.points,
+                    if game.has_ended() { ", ended" } else { "" },
+                    TIME_STEPS[time_step_idx].as_millis(),
+                );
+                self.term
+                    .queue(MoveTo(
+                        x_main,
+                        y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(format!("{status:^W_LIST$}").italic()))?;
+            }
+
+            for (dy, text) in [
+                "(Controls: [↓|↑]=scroll [←|→]=button [Space]=press/release)",
+                "([+|-]=shift [Alt]=shift all after [S]=step [Ins|Del])",
+                "([Enter]=watch [Ctrl+S]=save replay° [X]=export as text)",
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                self.term
+                    .queue(MoveTo(
+                        x_main,
+                        y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE + 1 + dy).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(format!("{text:^w_main$}").italic()))?;
+            }
+            if let Some(message) = message.take() {
+                self.term
+                    .queue(MoveTo(
+                        x_main,
+                        y_main + y_selection + 4 + u16::try_from(CAMERA_SIZE + 4).unwrap(),
+                    ))?
+                    .queue(PrintStyledContent(format!("{message:^w_main$}").italic()))?;
+            }
+            self.term.flush()?;
+
+            // Index of the earliest input that was changed, after which anchors have to be recalculated.
+            let mut edited_from: Option<usize> = None;
+
+            // Wait for new input.
+            match event::read()? {
+                // Quit menu.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c' | 'C'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: Press | Repeat,
+                    state: _,
+                }) => break Ok(MenuUpdate::Push(Menu::Quit)),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Esc | KeyCode::Char('q' | 'Q') | KeyCode::Backspace,
+                    kind: Press,
+                    ..
+                }) => {
+                    if *unsaved_edits {
+                        *unsaved_edits = false;
+                        message = Some("Edits not saved, press again to discard.".to_owned());
+                    } else {
+                        break Ok(MenuUpdate::Pop);
+                    }
+                }
+
+                // Move selector up.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up | KeyCode::Char('k' | 'K'),
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos = cursor_pos.saturating_sub(1);
+                }
+
+                // Move selector down.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Down | KeyCode::Char('j' | 'J'),
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos += 1;
+                }
+
+                // Move selector a page up or down.
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageUp,
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos = cursor_pos.saturating_sub(CAMERA_SIZE);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageDown,
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos += CAMERA_SIZE;
+                }
+
+                // Move selector top/bottom.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Home,
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos = 0;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::End,
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    *cursor_pos = n_inputs.saturating_sub(1);
+                }
+
+                // Change the button of the selected input.
+                Event::Key(KeyEvent {
+                    code:
+                        code @ (KeyCode::Left
+                        | KeyCode::Char('h' | 'H')
+                        | KeyCode::Right
+                        | KeyCode::Char('l' | 'L')),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_inputs > 0 => {
+                    let (_, input) = &mut game_restoration_data.input_history[*cursor_pos];
+                    let (Input::Activate(button) | Input::Deactivate(button)) = input;
+                    let n_buttons = Button::VARIANTS.len();
+                    let offset = if matches!(code, KeyCode::Left | KeyCode::Char('h' | 'H')) {
+                        n_buttons - 1
+                    } else {
+                        1
+                    };
+                    *button = Button::VARIANTS[(*button as usize + offset) % n_buttons];
+                    edited_from = Some(*cursor_pos);
+                }
+
+                // Toggle whether the selected input presses or releases its button.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(' '),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_inputs > 0 => {
+                    let (_, input) = &mut game_restoration_data.input_history[*cursor_pos];
+                    *input = match *input {
+                        Input::Activate(button) => Input::Deactivate(button),
+                        Input::Deactivate(button) => Input::Activate(button),
+                    };
+                    edited_from = Some(*cursor_pos);
+                }
+
+                // Shift the selected input in time (with [Alt]: together with all inputs after it).
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c @ ('+' | '=' | '-' | '_')),
+                    modifiers,
+                    kind: Press | Repeat,
+                    ..
+                }) if n_inputs > 0 => {
+                    let input_history = &mut game_restoration_data.input_history;
+                    let step = TIME_STEPS[time_step_idx];
+                    let shift_all_after = modifiers.contains(KeyModifiers::ALT);
+                    // Inputs stay in order of time.
+                    let earliest = cursor_pos
+                        .checked_sub(1)
+                        .map_or(InGameTime::ZERO, |i| input_history[i].0);
+                    let latest = input_history
+                        .get(*cursor_pos + 1)
+                        .filter(|_| !shift_all_after)
+                        .map_or(InGameTime::MAX, |(update_time, _)| *update_time);
+
+                    let update_time = input_history[*cursor_pos].0;
+                    let new_update_time = if matches!(c, '+' | '=') {
+                        update_time.saturating_add(step).min(latest)
+                    } else {
+                        update_time.saturating_sub(step).max(earliest)
+                    };
+
+                    if shift_all_after {
+                        for (update_time_after, _) in &mut input_history[*cursor_pos..] {
+                            *update_time_after = if new_update_time >= update_time {
+                                update_time_after.saturating_add(new_update_time - update_time)
+                            } else {
+                                update_time_after.saturating_sub(update_time - new_update_time)
+                            };
+                        }
+                    } else {
+                        input_history[*cursor_pos].0 = new_update_time;
+                    }
+                    edited_from = Some(*cursor_pos);
+                }
+
+                // Change the amount of time to shift by.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('s' | 'S'),
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    kind: Press,
+                    ..
+                }) => {
+                    time_step_idx = (time_step_idx + 1) % TIME_STEPS.len();
+                }
+
+                // Insert a copy of the selected input after it.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Insert | KeyCode::Char('i' | 'I'),
+                    kind: Press | Repeat,
+                    ..
+                }) => {
+                    let input_history = &mut game_restoration_data.input_history;
+                    if let Some(selected_input) = input_history.get(*cursor_pos).copied() {
+                        *cursor_pos += 1;
+                        input_history.insert(*cursor_pos, selected_input);
+                    } else {
+                        input_history.push((InGameTime::ZERO, Input::Activate(Button::MoveLeft)));
+                    }
+                    edited_from = Some(*cursor_pos);
+                }
+
+                // Delete the selected input.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Delete | KeyCode::Char('d' | 'D'),
+                    kind: Press | Repeat,
+                    ..
+                }) if n_inputs > 0 => {
+                    game_restoration_data.input_history.remove(*cursor_pos);
+                    edited_from = Some(*cursor_pos);
+                }
+
+                // Watch the edited inputs as replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Enter | KeyCode::Char('e' | 'E'),
+                    kind: Press,
+                    ..
+                }) => {
+                    let (game, game_restoration_data) =
+                        simulate_to_end(game_restoration_data, &mut game_save_anchors);
+
+                    let mut game_meta_data = game_meta_data.clone();
+                    game_meta_data.tool_assisted = true;
+
+                    break Ok(MenuUpdate::Push(Menu::ReplayGame {
+                        game_restoration_data: Box::new(game_restoration_data),
+                        game_meta_data,
+                        replay_length: game.state().time,
+                        game_renderer: TetroTUIRenderer::with_number(self.temp_data.renderernumber)
+                            .into(),
+                    }));
+                }
+
+                // Export the edited inputs in the text format.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('x' | 'X'),
+                    kind: Press,
+                    ..
+                }) => {
+                    message = Some(
+                        match Self::export_replay_text(game_restoration_data, game_meta_data) {
+                            Ok(path) => format!("Exported to {}", path.display()),
+                            Err(e) => format!("Export failed: {e}"),
+                        },
+                    );
+                }
+
+                // Save the edited inputs as new replay.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('s' | 'S'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: Press,
+                    ..
+                }) => {
+                    let (game, game_restoration_data) =
+                        simulate_to_end(game_restoration_data, &mut game_save_anchors);
+
+                    let mut game_meta_data = game_meta_data.clone();
+                    game_meta_data.datetime =
+                        chrono::Utc::now().format("%Y-%m-%d_%H:%M").to_string();
+                    game_meta_data.official_daily = false;
+                    game_meta_data.tool_assisted = true;
+
+                    let splits =
+                        SplitMilestones::for_game(&game_meta_data).map(|split_milestones| {
+                            let splits =
+                                game_restoration_data.splits(split_milestones, game.state().time);
+                            (split_milestones, splits)
+                        });
+                    let scores_entry = ScoreEntry::new(&game, game_meta_data, splits);
+                    let game_restoration_data =
+                        game_restoration_data.map(|h| CompressedInputHistory::new(&h));
+                    self.scores_and_replays
+                        .entries
+                        .push((scores_entry, Some(game_restoration_data)));
+
+                    *unsaved_edits = false;
+                    message = Some("Saved to 'Scores and Replays'.".to_owned());
+                }
+
+                // Other event: don't care.
+                _ => {}
+            }
+
+            if let Some(idx) = edited_from {
+                *unsaved_edits = true;
+                game_save_anchors.retain(|anchor| anchor.inputs_loaded <= idx);
+                selected_game = None;
+            }
+        }
+    }
+}
@@ -25,6 +25,7 @@ impl<T: Write> Application<T> {
                 cursor_pos: 0,
                 camera_pos: 0,
             },
+            Menu::Savepoints,
             Menu::Statistics,
             Menu::About,
             Menu::Quit,
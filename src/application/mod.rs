@@ -1,6 +1,11 @@
 mod menus;
+mod replay_export;
+mod replay_text;
 mod savefile_load_store;
 
+pub use replay_export::verify_replay_file;
+pub use replay_text::decode_replay_text;
+
 use std::{
     fmt::Debug,
     io::{self, Write},
@@ -17,8 +22,9 @@ use crossterm::{
 };
 
 use falling_tetromino_engine::{
-    Board, Button, DelayParameters, ExtDuration, Game, GameBuilder, GameEndCause, InGameTime,
-    Input, Notification, NotificationFeed, NotificationLevel, Stat, Tetromino,
+    Board, Button, DelayParameters, ExtDuration, Game, GameBuilder, GameEndCause, GameLimits,
+    InGameTime, Input, Notification, NotificationFeed, NotificationLevel, Phase, Piece, Stat,
+    Tetromino, UpdateGameError,
 };
 
 use crate::{
@@ -26,7 +32,7 @@ use crate::{
         menus::{Menu, MenuUpdate},
         savefile_load_store::SavefileGranularity,
     },
-    fmt_helpers::arabic_to_roman,
+    fmt_helpers::{arabic_to_roman, fmt_duration, tetromino_from_char},
     game_modes::{self, game_modifiers, GameMode},
     gameplay_settings::*,
     graphics_settings::*,
@@ -65,19 +71,24 @@ impl<T: Clone> SlotMachine<T> {
         slot_idx.lt(&self.unmodifiable).then(|| {
             let cloned_slot_content = self.slots[slot_idx].1.clone();
 
-            let mut n = 1;
-            let cloned_slot_name = loop {
-                let name = format!("{} {}", self.clone_name_template, arabic_to_roman(n));
-                if self.slots.iter().all(|s| s.0 != name) {
-                    break name;
-                }
-                n += 1;
-            };
+            self.push_slot(cloned_slot_content)
+        })
+    }
 
-            self.slots.push((cloned_slot_name, cloned_slot_content));
+    /// Appends a new (modifiable) slot with a generated name, and returns its index.
+    pub fn push_slot(&mut self, slot_content: T) -> usize {
+        let mut n = 1;
+        let slot_name = loop {
+            let name = format!("{} {}", self.clone_name_template, arabic_to_roman(n));
+            if self.slots.iter().all(|s| s.0 != name) {
+                break name;
+            }
+            n += 1;
+        };
 
-            self.slots.len() - 1
-        })
+        self.slots.push((slot_name, slot_content));
+
+        self.slots.len() - 1
     }
 }
 
@@ -89,22 +100,96 @@ pub type UncompressedInputHistory = Vec<(InGameTime, Input)>;
 /// Compressed verson of an input history.
 ///
 /// Currently done using deltatime and assumption that inputs have millisecond precision at most.
-#[derive(
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    Clone,
-    Debug,
-    Default,
-    serde::Serialize,
-    serde::Deserialize,
-)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Default)]
+#[serde_with::serde_as] // Do **NOT** place this after #[derive(..)] !!
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct CompressedInputHistory {
+    #[serde_as(as = "InputBufDummyType")]
     inputbuf: Vec<u128>,
 }
 
+/// Stores the compressed inputs as base64 string of their varint (LEB128) encodings,
+/// instead of a much longer list of decimal numbers.
+///
+/// Savefiles from before this change, which contain such a list, can still be read.
+struct InputBufDummyType;
+
+impl InputBufDummyType {
+    fn encode(inputbuf: &[u128]) -> String {
+        let mut bytes = Vec::new();
+        for i in inputbuf {
+            let mut i = *i;
+            while i >= 0x80 {
+                bytes.push(u8::try_from(i & 0x7f).unwrap() | 0x80);
+                i >>= 7;
+            }
+            bytes.push(u8::try_from(i).unwrap());
+        }
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Vec<u128>, String> {
+        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded)
+            .map_err(|e| e.to_string())?;
+        let mut inputbuf = Vec::new();
+        let mut i = 0u128;
+        let mut shift = 0;
+        for byte in bytes.iter() {
+            if shift >= 128 || (shift > 0 && u128::from(byte & 0x7f) >> (128 - shift) != 0) {
+                return Err("input value out of range".to_owned());
+            }
+            i |= u128::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                inputbuf.push(i);
+                i = 0;
+                shift = 0;
+            } else {
+                shift += 7;
+            }
+        }
+        if shift != 0 {
+            return Err("truncated input value".to_owned());
+        }
+        Ok(inputbuf)
+    }
+}
+
+impl serde_with::SerializeAs<Vec<u128>> for InputBufDummyType {
+    fn serialize_as<S: serde::ser::Serializer>(
+        inputbuf: &Vec<u128>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&Self::encode(inputbuf))
+    }
+}
+
+impl<'de> serde_with::DeserializeAs<'de, Vec<u128>> for InputBufDummyType {
+    fn deserialize_as<D: serde::de::Deserializer<'de>>(d: D) -> Result<Vec<u128>, D::Error> {
+        struct InputBufVisitor;
+        impl<'de> serde::de::Visitor<'de> for InputBufVisitor {
+            type Value = Vec<u128>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("base64 string of varint-encoded inputs, or list of inputs")
+            }
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Vec<u128>, E> {
+                InputBufDummyType::decode(value).map_err(E::custom)
+            }
+            // Backwards compatibility: inputs stored as list of numbers.
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Vec<u128>, A::Error> {
+                let mut inputbuf = Vec::new();
+                while let Some(i) = seq.next_element()? {
+                    inputbuf.push(i);
+                }
+                Ok(inputbuf)
+            }
+        }
+        d.deserialize_any(InputBufVisitor)
+    }
+}
+
 impl CompressedInputHistory {
     // How many bits it takes to encode a `ButtonChange`:
     // - 1 bit for Press/Release,
@@ -184,6 +269,12 @@ impl CompressedInputHistory {
     }
 }
 
+/// A game re-simulated up to some point, to continue re-simulating from instead of restoring the game anew.
+struct GameSaveAnchor {
+    game: Game,
+    inputs_loaded: usize,
+}
+
 /// All the data required to functionally reconstruct gameplay.
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
@@ -260,13 +351,123 @@ impl GameRestorationData<UncompressedInputHistory> {
         game.config.notification_level = NotificationLevel::Silent;
         for (update_time, button_change) in self.input_history.iter().take(input_index) {
             // FIXME: Handle UpdateGameError? If not, why not?
-            let _v = game.update(*update_time, Some(*button_change));
+            let _v =
+                game_modifiers::Sandbox::update_game(&mut game, *update_time, Some(*button_change));
         }
 
         game.config.notification_level = restore_notification_level;
 
         game
     }
+
+    /// Re-simulate a restored game further: Load the recorded inputs up to the target time (but no more than `inputs_to_load` in total), then update the game to the target time.
+    ///
+    /// The feed of every update is handed to `on_update`, together with the index of the input loaded by it if any.
+    /// Inputs which the game rejects for being out of order are skipped over, like when replaying.
+    fn resimulate(
+        &self,
+        game: &mut Game,
+        inputs_loaded: &mut usize,
+        inputs_to_load: usize,
+        target_time: InGameTime,
+        mut on_update: impl FnMut(&Game, Option<usize>, NotificationFeed),
+    ) {
+        while *inputs_loaded < inputs_to_load {
+            let Some((update_time, input)) = self.input_history.get(*inputs_loaded) else {
+                break;
+            };
+            if target_time < *update_time {
+                break;
+            }
+
+            match game_modifiers::Sandbox::update_game(game, *update_time, Some(*input)) {
+                Ok(msgs) => on_update(game, Some(*inputs_loaded), msgs),
+                Err(UpdateGameError::TargetTimeInPast) => {}
+                Err(UpdateGameError::AlreadyEnded) => return,
+            }
+
+            *inputs_loaded += 1;
+        }
+
+        if let Ok(msgs) = game_modifiers::Sandbox::update_game(game, target_time, None) {
+            on_update(game, None, msgs);
+        }
+    }
+
+    /// Reconstruct the game at the given time, starting from the latest anchor before it if available.
+    ///
+    /// Returns the game together with the number of inputs loaded.
+    fn seek(
+        &self,
+        game_save_anchors: Option<&[GameSaveAnchor]>,
+        target_time: InGameTime,
+    ) -> (Game, usize) {
+        let latest_anchor = game_save_anchors.and_then(|anchors| {
+            anchors
+                .iter()
+                .rfind(|anchor| anchor.game.state().time <= target_time)
+        });
+        let (mut game, mut inputs_loaded) = if let Some(GameSaveAnchor {
+            game: anchor_game,
+            inputs_loaded: anchor_inputs_loaded,
+        }) = latest_anchor
+        {
+            (anchor_game.try_clone().unwrap(), *anchor_inputs_loaded)
+        } else {
+            (self.restore(0), 0)
+        };
+
+        self.resimulate(
+            &mut game,
+            &mut inputs_loaded,
+            self.input_history.len(),
+            target_time,
+            |_game, _input_idx, _msgs| {},
+        );
+
+        (game, inputs_loaded)
+    }
+
+    /// Find how many inputs had been done before the most recent piece lock up until the given time.
+    fn inputs_before_last_lock(&self, until: InGameTime) -> Option<usize> {
+        let mut game = self.restore(0);
+        let mut inputs_loaded = 0;
+
+        let mut inputs_before_last_lock = None;
+        self.resimulate(
+            &mut game,
+            &mut inputs_loaded,
+            self.input_history.len(),
+            until,
+            |_game, input_idx, msgs| {
+                // Piece may lock by itself before the input, or due to the input.
+                if msgs.iter().any(|(notification, _)| {
+                    matches!(notification, Notification::PieceLocked { .. })
+                }) {
+                    inputs_before_last_lock = Some(input_idx.unwrap_or(self.input_history.len()));
+                }
+            },
+        );
+
+        inputs_before_last_lock
+    }
+
+    /// Find the times at which each split milestone was reached, up until the given time.
+    fn splits(&self, split_milestones: SplitMilestones, until: InGameTime) -> Vec<InGameTime> {
+        let mut game = self.restore(0);
+        let mut inputs_loaded = 0;
+        let mut split_recorder = SplitRecorder::new(split_milestones, &game);
+
+        self.resimulate(
+            &mut game,
+            &mut inputs_loaded,
+            self.input_history.len(),
+            until,
+            |_game, _input_idx, msgs| split_recorder.record(&msgs),
+        );
+
+        split_recorder.finish().1
+    }
 }
 
 /// Data associated with a Tetro TUI game.
@@ -277,6 +478,56 @@ pub struct GameMetaData {
     pub datetime: String,
     pub title: String,
     pub comparison_stat: (Stat, bool),
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forked_from: Option<ReplayFork>,
+    /// Whether pieces were undone during the game, which excludes it from leaderboards and statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub used_undo: bool,
+    /// Whether this is the official attempt of the day's daily challenge.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub official_daily: bool,
+    /// Whether the inputs were crafted in the TAS editor, which excludes it from leaderboards and statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tool_assisted: bool,
+    /// Whether an external bot played during the game, which excludes it from leaderboards and statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bot_played: bool,
+    /// Whether the solver showed where to place pieces during the game, which excludes it from leaderboards and statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub used_solver: bool,
+    /// Whether the game is played in puzzle stages (e.g. imported from a fumen), which are not counted toward statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub puzzle: bool,
+}
+
+impl GameMetaData {
+    /// The title of the mode played, without the marks of resumed or forked games.
+    pub fn mode_title(&self) -> &str {
+        self.title.trim_end_matches('\'')
+    }
+
+    /// The title of the underlying mode, which the daily challenge is played in, too.
+    pub fn base_mode_title(&self) -> &str {
+        let mode_title = self.mode_title();
+        mode_title
+            .strip_prefix(GameMode::TITLE_DAILY)
+            .map_or(mode_title, str::trim_start)
+    }
+
+    /// Whether the game competes on leaderboards and counts towards statistics.
+    pub fn is_ranked(&self) -> bool {
+        !self.used_undo && !self.tool_assisted && !self.bot_played && !self.used_solver
+    }
+}
+
+/// Where a game was forked off from a replay.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub struct ReplayFork {
+    pub parent_datetime: String,
+    pub parent_title: String,
+    pub fork_time: InGameTime,
 }
 
 // FIXME: Currently an ad-hoc struct to store game saves.
@@ -285,6 +536,8 @@ pub struct GameMetaData {
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
 )]
 pub struct GameSave<T> {
+    #[serde(default)]
+    name: String,
     game_meta_data: GameMetaData,
     game_restoration_data: GameRestorationData<T>,
     inputs_to_load: usize,
@@ -293,6 +546,7 @@ pub struct GameSave<T> {
 impl<T> GameSave<T> {
     fn map<U>(self, f: impl Fn(T) -> U) -> GameSave<U> {
         GameSave {
+            name: self.name,
             game_restoration_data: self.game_restoration_data.map(f),
             game_meta_data: self.game_meta_data,
             inputs_to_load: self.inputs_to_load,
@@ -300,6 +554,32 @@ impl<T> GameSave<T> {
     }
 }
 
+impl GameSave<UncompressedInputHistory> {
+    /// Restore the saved game, to be continued as new game.
+    fn load(&self) -> (GameMetaData, Game, UncompressedInputHistory) {
+        let restored_game = self.game_restoration_data.restore(self.inputs_to_load);
+
+        let mut restored_game_meta_data = self.game_meta_data.clone();
+        // Mark restored game as such.
+        restored_game_meta_data.title.push('\'');
+        restored_game_meta_data.official_daily = false;
+
+        let restored_input_history = self
+            .game_restoration_data
+            .input_history
+            .iter()
+            .take(self.inputs_to_load)
+            .copied()
+            .collect();
+
+        (
+            restored_game_meta_data,
+            restored_game,
+            restored_input_history,
+        )
+    }
+}
+
 /// An entry for the scoreboard. Store all the basic, cheap stats required for proper scoreboard entry display and sorting.
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
@@ -314,6 +594,159 @@ pub struct ScoreEntry {
     pieces_locked: [u32; Tetromino::VARIANTS.len()],
     fall_delay_reached: ExtDuration,
     lock_delay_reached: Option<ExtDuration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    splits: Option<(SplitMilestones, Vec<InGameTime>)>,
+}
+
+impl ScoreEntry {
+    /// Summarize an ended game for the scoreboard, with split times if its mode is played for time.
+    fn new(
+        game: &Game,
+        game_meta_data: GameMetaData,
+        splits: Option<(SplitMilestones, Vec<InGameTime>)>,
+    ) -> Self {
+        let (end_cause, is_win) = match game.phase() {
+            Phase::GameEnd { cause, is_win } => (cause.clone(), *is_win),
+            _ => (
+                GameEndCause::Forfeit {
+                    piece_in_play: None,
+                },
+                false,
+            ),
+        };
+
+        ScoreEntry {
+            game_meta_data,
+            is_win,
+            end_cause,
+            time_elapsed: game.state().time,
+            pieces_locked: game.state().pieces_locked,
+            lineclears: game.state().lineclears,
+            fall_delay_reached: game.state().fall_delay,
+            lock_delay_reached: (game
+                .state()
+                .fall_delay_lowerbound_hit_at_n_lineclears
+                .is_some()
+                && !game.config.lock_delay_params.is_constant())
+            .then_some(game.state().lock_delay),
+            points_scored: game.state().points,
+            splits,
+        }
+    }
+}
+
+/// The points of a game at which split times are taken.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub enum SplitMilestones {
+    EveryNLines(u32),
+    PuzzleStages,
+}
+
+impl SplitMilestones {
+    pub const DEFAULT_LINES: u32 = 10;
+
+    /// The milestones that splits are taken at in the given game, if it is played for time.
+    pub fn for_game(game_meta_data: &GameMetaData) -> Option<Self> {
+        if !matches!(game_meta_data.comparison_stat.0, Stat::TimeElapsed(_)) {
+            return None;
+        }
+
+        if game_meta_data.base_mode_title() == GameMode::TITLE_PUZZLE {
+            Some(Self::PuzzleStages)
+        } else {
+            Some(Self::EveryNLines(Self::DEFAULT_LINES))
+        }
+    }
+
+    /// Short label of the milestone of the split with the given index.
+    pub fn label(&self, split_idx: usize) -> String {
+        match self {
+            Self::EveryNLines(n) => format!("{}L", (split_idx + 1) * usize::try_from(*n).unwrap()),
+            Self::PuzzleStages => format!("S{}", split_idx + 1),
+        }
+    }
+}
+
+/// Takes the split times of a game from the feeds of its updates.
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct SplitRecorder {
+    split_milestones: SplitMilestones,
+    lineclears: u32,
+    splits: Vec<InGameTime>,
+}
+
+impl SplitRecorder {
+    fn new(split_milestones: SplitMilestones, game: &Game) -> Self {
+        Self {
+            split_milestones,
+            lineclears: game.state().lineclears,
+            splits: Vec::new(),
+        }
+    }
+
+    /// Continue taking splits of a game in progress, re-simulating it to find the splits taken so far.
+    fn resume(
+        split_milestones: SplitMilestones,
+        game: &Game,
+        game_input_history: &UncompressedInputHistory,
+    ) -> Self {
+        let splits = if game.state().time.is_zero() {
+            Vec::new()
+        } else {
+            GameRestorationData::new(game, game_input_history.clone(), None)
+                .splits(split_milestones, game.state().time)
+        };
+
+        Self {
+            splits,
+            ..Self::new(split_milestones, game)
+        }
+    }
+
+    /// Take splits at the milestones reached in the feed of an update.
+    fn record(&mut self, feed: &NotificationFeed) {
+        match self.split_milestones {
+            SplitMilestones::EveryNLines(n) => {
+                for (notification, time) in feed {
+                    if let Notification::LinesClearing {
+                        y_coords,
+                        line_clear_duration,
+                    } = notification
+                    {
+                        // Lines only count as cleared once the line clear has finished.
+                        self.lineclears += u32::try_from(y_coords.len()).unwrap();
+                        while self.lineclears / n > u32::try_from(self.splits.len()).unwrap() {
+                            self.splits.push(time.saturating_add(*line_clear_duration));
+                        }
+                    }
+                }
+            }
+            SplitMilestones::PuzzleStages => {
+                for (notification, time) in feed {
+                    if let Some(stage_idx) = game_modifiers::Puzzle::stage_cleared(notification) {
+                        self.splits.truncate(stage_idx);
+                        self.splits.push(*time);
+                    }
+                }
+            }
+        }
+    }
+
+    fn splits(&self) -> &[InGameTime] {
+        &self.splits
+    }
+
+    /// Drop the splits taken after the point the game was set back to (e.g. undo).
+    fn set_back(&mut self, game: &Game) {
+        self.splits.retain(|split| *split <= game.state().time);
+        self.lineclears = game.state().lineclears;
+    }
+
+    fn finish(self) -> (SplitMilestones, Vec<InGameTime>) {
+        (self.split_milestones, self.splits)
+    }
 }
 
 #[derive(
@@ -334,6 +767,15 @@ pub struct Scoreboard {
         Option<GameRestorationData<CompressedInputHistory>>,
     )>,
     sorting: ScoreEntrySorting,
+    /// Official attempts at the daily challenge, at most one per day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    daily_entries: Vec<(
+        ScoreEntry,
+        Option<GameRestorationData<CompressedInputHistory>>,
+    )>,
+    /// The date on which the official daily attempt was last started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    daily_last_attempted: Option<String>,
 }
 
 impl Default for Scoreboard {
@@ -341,6 +783,8 @@ impl Default for Scoreboard {
         Self {
             sorting: ScoreEntrySorting::ModeDependent,
             entries: Vec::new(),
+            daily_entries: Vec::new(),
+            daily_last_attempted: None,
         }
     }
 }
@@ -354,6 +798,67 @@ impl Scoreboard {
         }
     }
 
+    /// The fastest time of each segment between splits among all games of the given mode, except the given entry.
+    pub fn best_segments(
+        &self,
+        mode_title: &str,
+        split_milestones: SplitMilestones,
+        excluded_entry: Option<&ScoreEntry>,
+    ) -> Vec<InGameTime> {
+        let mut best_segments: Vec<InGameTime> = Vec::new();
+        for (entry, _) in &self.entries {
+            if !entry.game_meta_data.is_ranked()
+                || entry.game_meta_data.mode_title() != mode_title
+                || excluded_entry.is_some_and(|excluded| excluded == entry)
+            {
+                continue;
+            }
+            let Some((milestones, splits)) = &entry.splits else {
+                continue;
+            };
+            if *milestones != split_milestones {
+                continue;
+            }
+            let mut prev_split = InGameTime::ZERO;
+            for (i, split) in splits.iter().enumerate() {
+                let segment = split.saturating_sub(prev_split);
+                prev_split = *split;
+                if let Some(best_segment) = best_segments.get_mut(i) {
+                    *best_segment = segment.min(*best_segment);
+                } else {
+                    best_segments.push(segment);
+                }
+            }
+        }
+        best_segments
+    }
+
+    /// The best possible time of the given mode, as sum of its best segments.
+    pub fn sum_of_best(
+        &self,
+        mode_title: &str,
+        split_milestones: SplitMilestones,
+    ) -> Option<InGameTime> {
+        // Only as many segments as a completed game has count towards the sum.
+        let n_segments = self
+            .entries
+            .iter()
+            .filter(|(entry, _)| {
+                entry.is_win
+                    && entry.game_meta_data.is_ranked()
+                    && entry.game_meta_data.mode_title() == mode_title
+            })
+            .filter_map(|(entry, _)| match &entry.splits {
+                Some((milestones, splits)) if *milestones == split_milestones => Some(splits.len()),
+                _ => None,
+            })
+            .max()?;
+
+        let best_segments = self.best_segments(mode_title, split_milestones, None);
+
+        (best_segments.len() >= n_segments).then(|| best_segments[..n_segments].iter().sum())
+    }
+
     fn sort_chronologically(&mut self) {
         self.entries.sort_by(|(pg1, _), (pg2, _)| {
             pg1.game_meta_data
@@ -368,6 +873,8 @@ impl Scoreboard {
         self.entries.sort_by(|(pg1, _), (pg2, _)|
             // Sort by gamemode (name).
             pg1.game_meta_data.title.cmp(&pg2.game_meta_data.title).then_with(||
+            // Sort games that used undo or tools last, they do not compete.
+            pg1.game_meta_data.is_ranked().cmp(&pg2.game_meta_data.is_ranked()).reverse().then_with(||
             // Sort by if gamemode was finished successfully.
             pg1.is_win.cmp(&pg2.is_win).reverse().then_with(|| {
                 // Sort by comparison stat...
@@ -384,7 +891,7 @@ impl Scoreboard {
                 if pg1.game_meta_data.comparison_stat.1
                     { o } else { o.reverse() }
             })
-            )
+            ))
         );
     }
 
@@ -428,7 +935,11 @@ pub struct Statistics {
 
 impl Statistics {
     // This simple blacklist is used to prevent certain game modes from being counted toward stats (e.g. Puzzle's perfect clears).
-    const BLACKLIST_TITLE_PREFIXES: &[&str] = &[GameMode::TITLE_PUZZLE, GameMode::TITLE_COMBO];
+    const BLACKLIST_TITLE_PREFIXES: &[&str] = &[
+        GameMode::TITLE_PUZZLE,
+        GameMode::TITLE_COMBO,
+        GameMode::TITLE_SANDBOX,
+    ];
 
     fn accumulate_from_feed(&mut self, feed: &NotificationFeed) {
         for (notification, _notif_time) in feed {
@@ -502,9 +1013,19 @@ impl Statistics {
 )]
 pub struct NewGameSettings {
     custom_fall_delay_params: DelayParameters,
+    #[serde(default)]
+    custom_limits: GameLimits,
+    /// Win condition of custom games in older savefiles, which is converted into `custom_limits` on load.
+    #[serde(default, skip_serializing)]
     custom_win_condition: Option<Stat>,
     custom_seed: Option<u64>,
-    custom_encoded_board: Option<String>, // For more compact serialization of NewGameSettings, we store an encoded `Board` (see `encode_board`).
+    custom_encoded_board: Option<String>, // For more compact serialization of NewGameSettings, we store an encoded `Board` (see `StartBoard::encode_board`).
+    /// Puzzle stages imported from a fumen, given by encoded board and the pieces to clear it with.
+    #[serde(default)]
+    custom_encoded_puzzle_stages: Option<Vec<(String, Vec<Tetromino>)>>,
+    /// Fixed piece sequence, see [`game_modifiers::PieceSequence`].
+    #[serde(default)]
+    custom_piece_sequence: Option<String>,
 
     cheese_tiles_per_line: NonZeroUsize,
     cheese_fall_lock_delays: (ExtDuration, ExtDuration),
@@ -523,9 +1044,12 @@ impl Default for NewGameSettings {
     fn default() -> Self {
         Self {
             custom_fall_delay_params: DelayParameters::standard_fall(),
+            custom_limits: GameLimits::new(),
             custom_win_condition: None,
             custom_seed: None,
             custom_encoded_board: None,
+            custom_encoded_puzzle_stages: None,
+            custom_piece_sequence: None,
 
             cheese_limit: Some(NonZeroU32::try_from(20).unwrap()),
             cheese_fall_lock_delays: (ExtDuration::Infinite, ExtDuration::Infinite),
@@ -541,48 +1065,220 @@ impl Default for NewGameSettings {
 }
 
 impl NewGameSettings {
-    #[allow(dead_code)]
-    pub fn encode_board(board: &Board) -> String {
-        board
+    /// Convert settings loaded from older savefiles.
+    fn migrate_legacy_fields(&mut self) {
+        if let Some(stat) = self.custom_win_condition.take() {
+            self.custom_limits = GameLimits::single(stat, true);
+        }
+    }
+
+    /// The currently configured custom game.
+    fn custom_game_preset(&self) -> CustomGamePreset {
+        CustomGamePreset {
+            fall_delay_params: self.custom_fall_delay_params,
+            limits: self.custom_limits,
+            seed: self.custom_seed,
+            encoded_board: self.custom_encoded_board.clone(),
+            encoded_puzzle_stages: self.custom_encoded_puzzle_stages.clone(),
+            piece_sequence: self.custom_piece_sequence.clone(),
+        }
+    }
+
+    /// Encode a board, optionally with the piece on it, as fumen string (see <https://fumen.zui.jp/>).
+    pub fn encode_fumen(board: &Board, piece: Option<&Piece>) -> String {
+        game_modes::fumen::encode(board, piece)
+    }
+
+    /// Decode a fumen string: A single page becomes a start board, several pages become puzzle stages.
+    ///
+    /// A stage starts at every page whose board was edited. Its pieces are those listed in a quiz comment
+    /// (e.g. `#Q=[](T)SZ`), or otherwise the pieces placed on its pages.
+    pub fn decode_fumen(fumen_str: &str) -> Result<FumenImport, String> {
+        let pages = game_modes::fumen::decode(fumen_str)?;
+
+        if let [page] = pages.as_slice() {
+            return Ok(FumenImport::Board(Box::new(page.board)));
+        }
+
+        let mut stages: Vec<(Board, Vec<Tetromino>)> = Vec::new();
+        let mut stage_is_quiz = false;
+        for (page_idx, page) in pages.into_iter().enumerate() {
+            if page_idx == 0 || page.field_changed {
+                let quiz_tetrominos = page.comment.strip_prefix("#Q=").map(|queue| {
+                    queue
+                        .chars()
+                        .take_while(|c| "[]()IJLOSTZ".contains(*c))
+                        .filter_map(tetromino_from_char)
+                        .collect::<Vec<_>>()
+                });
+                stage_is_quiz = quiz_tetrominos.is_some();
+                stages.push((page.board, quiz_tetrominos.unwrap_or_default()));
+            }
+            if let (false, Some(tet), Some((_, stage_tetrominos))) =
+                (stage_is_quiz, page.tetromino, stages.last_mut())
+            {
+                stage_tetrominos.push(tet);
+            }
+        }
+
+        if let Some(idx) = stages.iter().position(|(_, tets)| tets.is_empty()) {
+            return Err(format!("puzzle stage {} has no pieces", idx + 1));
+        }
+
+        Ok(FumenImport::Puzzle(stages))
+    }
+
+    /// Set up the custom game with a decoded fumen, replacing any previous custom board or puzzle.
+    pub fn load_fumen(&mut self, fumen_import: FumenImport) {
+        match fumen_import {
+            FumenImport::Board(board) => {
+                self.custom_encoded_board = Some(game_modifiers::StartBoard::encode_board(&board));
+                self.custom_encoded_puzzle_stages = None;
+            }
+            FumenImport::Puzzle(stages) => {
+                self.custom_encoded_board = None;
+                self.custom_encoded_puzzle_stages = Some(
+                    stages
+                        .into_iter()
+                        .map(|(board, tets)| {
+                            (game_modifiers::StartBoard::encode_board(&board), tets)
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+/// A board or puzzle imported from a fumen string, see [`NewGameSettings::decode_fumen`].
+#[derive(Clone, Debug)]
+pub enum FumenImport {
+    Board(Box<Board>),
+    Puzzle(Vec<(Board, Vec<Tetromino>)>),
+}
+
+/// The configuration of a custom game, which can be stored as a named mode.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub struct CustomGamePreset {
+    fall_delay_params: DelayParameters,
+    limits: GameLimits,
+    seed: Option<u64>,
+    encoded_board: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoded_puzzle_stages: Option<Vec<(String, Vec<Tetromino>)>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    piece_sequence: Option<String>,
+}
+
+impl CustomGamePreset {
+    /// Short description of the game's limits, used as title of unnamed custom games.
+    ///
+    /// Win limits are written as e.g. 'Lines-40', lose limits as e.g. 'Pieces<100'.
+    fn limit_title(&self) -> String {
+        let limit_titles = self
+            .limits
             .iter()
-            .map(|line| {
-                line.iter()
-                    .map(|tile| if tile.is_some() { 'X' } else { ' ' })
-                    .collect::<String>()
+            .map(|(stat, to_win)| {
+                let sep = if to_win { "-" } else { "<" };
+                match stat {
+                    Stat::TimeElapsed(duration) => format!("Time{sep}{}s", duration.as_secs()),
+                    Stat::PiecesLocked(p) => format!("Pieces{sep}{p}"),
+                    Stat::LinesCleared(l) => format!("Lines{sep}{l}"),
+                    Stat::PointsScored(s) => format!("Score{sep}{s}"),
+                }
             })
-            .collect::<String>()
-            .trim_end()
-            .to_owned()
+            .collect::<Vec<_>>();
+        if limit_titles.is_empty() {
+            "Limitless".to_owned()
+        } else {
+            limit_titles.join("/")
+        }
     }
 
-    pub fn decode_board(board_str: &str) -> Board {
-        let grey_tile = Some(std::num::NonZeroU8::try_from(254).unwrap());
+    /// Title of the custom game when played unnamed.
+    fn title(&self) -> String {
+        if self.encoded_puzzle_stages.is_some() {
+            format!("Fumen-{}", GameMode::TITLE_PUZZLE)
+        } else {
+            self.limit_title()
+        }
+    }
 
-        let mut new_board = Board::default();
+    fn build(&self, builder: &GameBuilder) -> Game {
+        let mut builder = builder.clone();
 
-        let mut chars = board_str.chars();
+        builder
+            .fall_delay_params(self.fall_delay_params)
+            .game_limits(self.limits);
 
-        for line in &mut new_board {
-            for tile in line {
-                for char in chars.by_ref() {
-                    if char == ' ' {
-                        // Space = empty tile.
-                        *tile = None;
-                        break;
-                    } else if char == '\n' {
-                        // Newline = ignore, stay at tile but move on to next char.
-                        continue;
-                    } else {
-                        // Otherwise = filled tile.
-                        *tile = grey_tile;
-                        break;
-                    }
-                }
+        // Make lock delay decrease if fall delay was chosen to decrease.
+        if !self.fall_delay_params.is_constant() {
+            builder.lock_delay_params(DelayParameters::standard_lock());
+        }
+
+        // Optionally load custom seed.
+        if let Some(seed) = self.seed {
+            builder.seed(seed);
+        }
+
+        let mut mods = Vec::new();
+
+        // Optionally load imported puzzle.
+        if let Some(encoded_puzzle_stages) = &self.encoded_puzzle_stages {
+            mods.push(game_modifiers::Puzzle::prepare(
+                &mut builder,
+                encoded_puzzle_stages.clone(),
+            ));
+        // Optionally load custom board.
+        } else if let Some(encoded_board) = &self.encoded_board {
+            mods.push(game_modifiers::StartBoard::prepare(
+                &mut builder,
+                encoded_board.clone(),
+            ));
+        }
+
+        // Optionally deal a fixed piece sequence (puzzles come with their own pieces).
+        if let (None, Some(piece_sequence)) = (&self.encoded_puzzle_stages, &self.piece_sequence) {
+            // Sequences are checked when entered, so this cannot fail.
+            if let Ok(modifier) = game_modifiers::PieceSequence::modifier(piece_sequence.clone()) {
+                mods.push(modifier);
             }
         }
 
-        new_board
+        builder.build_modded(mods)
     }
+
+    /// A custom game stored under the given name, which becomes its title in the scoreboard.
+    fn into_game_mode(self, name: String) -> GameMode {
+        GameMode {
+            title: name,
+            description: format!(
+                "{}{}{}{} [R]=rename [Del]=delete",
+                self.limit_title(),
+                if self.seed.is_some() { " *seed" } else { "" },
+                if self.piece_sequence.is_some() {
+                    " *queue"
+                } else {
+                    ""
+                },
+                if self.encoded_board.is_some() {
+                    " *board"
+                } else if self.encoded_puzzle_stages.is_some() {
+                    " *puzzle"
+                } else {
+                    ""
+                },
+            ),
+            stat_and_order_desc: (Stat::PointsScored(0), false),
+            build: Box::new(move |builder: &GameBuilder| self.build(builder)),
+        }
+    }
+}
+
+fn default_custom_game_slots() -> SlotMachine<CustomGamePreset> {
+    SlotMachine::with_unmodifiable_slots(Vec::new(), "Custom".to_owned())
 }
 
 // #[serde_with::serde_as]
@@ -597,6 +1293,8 @@ pub struct Settings {
     keybinds_slotmachine: SlotMachine<Keybinds>,
     gameplay_slotmachine: SlotMachine<GameplaySettings>,
     palette_slotmachine: SlotMachine<Palette>,
+    #[serde(default = "default_custom_game_slots")]
+    custom_game_slotmachine: SlotMachine<CustomGamePreset>,
 }
 
 impl Default for Settings {
@@ -610,6 +1308,7 @@ impl Default for Settings {
             palette_slotmachine: default_palette_slots(),
             keybinds_slotmachine: default_keybinds_slots(),
             gameplay_slotmachine: default_gameplay_slots(),
+            custom_game_slotmachine: default_custom_game_slots(),
         }
     }
 }
@@ -651,7 +1350,11 @@ pub struct TemporaryAppData {
     pub renderernumber: usize,
     pub save_on_exit: SavefileGranularity,
     pub savefile_path: PathBuf, // This should technically be the same for a given compiled binary, but we compute it at runtime.
+    pub modes_dir: PathBuf,     // Directory of mode files listed in the New Game menu.
     pub loadfile_result: io::Result<()>,
+    pub bot_cmd: Option<String>,
+    // Replay to open in the TAS editor on startup.
+    pub replay_import: Option<(GameRestorationData<UncompressedInputHistory>, GameMetaData)>,
 }
 
 // FIXME: Move tui application into `main` instead of artifically having it in one module below `tetro-tui::main`?
@@ -667,7 +1370,7 @@ pub struct Application<T: Write> {
 
     statistics: Statistics,
 
-    // FIXME: Currently one can only access one without resorting to manually editing the savefile.
+    /// Index of the currently selected savepoint, and all named savepoints (browsable in the savepoints menu).
     game_saves: (usize, Vec<GameSave<UncompressedInputHistory>>),
 }
 
@@ -708,6 +1411,71 @@ impl<T: Write> Application<T> {
     pub const GAME_KEYBOARD_ENHANCEMENT_FLAGS: KeyboardEnhancementFlags =
         KeyboardEnhancementFlags::all();
 
+    /// Add a new savepoint of the game and select it, returning the savepoint's name.
+    fn store_game_save(
+        &mut self,
+        game_meta_data: &GameMetaData,
+        game: &Game,
+        input_history: UncompressedInputHistory,
+        inputs_to_load: usize,
+    ) -> String {
+        let name = format!(
+            "{} @{}",
+            game_meta_data.title,
+            fmt_duration(game.state().time)
+        );
+
+        let forfeit = matches!(
+            game.phase(),
+            Phase::GameEnd {
+                cause: GameEndCause::Forfeit { .. },
+                ..
+            }
+        )
+        .then_some(game.state().time);
+
+        self.game_saves.1.push(GameSave {
+            name: name.clone(),
+            game_meta_data: game_meta_data.clone(),
+            game_restoration_data: GameRestorationData::new(game, input_history, forfeit),
+            inputs_to_load,
+        });
+        self.game_saves.0 = self.game_saves.1.len() - 1;
+
+        name
+    }
+
+    /// Find the splits of the fastest completed game of the same mode, to compare a live game against.
+    fn best_splits(
+        &self,
+        game_meta_data: &GameMetaData,
+    ) -> Option<(SplitMilestones, Vec<InGameTime>)> {
+        let split_milestones = SplitMilestones::for_game(game_meta_data)?;
+        let mode_title = game_meta_data.mode_title();
+
+        let (best_entry, restoration_data) = self
+            .scores_and_replays
+            .entries
+            .iter()
+            .filter(|(entry, _)| {
+                entry.is_win
+                    && entry.game_meta_data.is_ranked()
+                    && entry.game_meta_data.mode_title() == mode_title
+            })
+            .min_by_key(|(entry, _)| entry.time_elapsed)?;
+
+        // Games recorded before splits existed need to be re-simulated.
+        let splits = match &best_entry.splits {
+            Some((milestones, splits)) if *milestones == split_milestones => splits.clone(),
+            _ => restoration_data
+                .clone()?
+                .map(|h| h.decompress())
+                .splits(split_milestones, best_entry.time_elapsed),
+        };
+
+        Some((split_milestones, splits))
+    }
+
     pub fn fetch_main_xy() -> (u16, u16) {
         let (w_console, h_console) = terminal::size().unwrap_or((0, 0));
         (
@@ -767,11 +1535,17 @@ impl<T: Write> Application<T> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn with_savefile_and_cmdlineoptions(
         term: T,
         savefile_path: PathBuf,
+        modes_dir: PathBuf,
         custom_start_seed: Option<u64>,
         custom_start_board: Option<String>,
+        custom_start_fumen: Option<FumenImport>,
+        custom_piece_sequence: Option<String>,
+        bot_cmd: Option<String>,
+        replay_import: Option<(GameRestorationData<UncompressedInputHistory>, GameMetaData)>,
     ) -> Self {
         // Now that the settings are loaded, we handle separate flags set for this session.
         let kitty_detected = terminal::supports_keyboard_enhancement().unwrap_or(false);
@@ -784,7 +1558,10 @@ impl<T: Write> Application<T> {
             renderernumber: 0,
             save_on_exit: SavefileGranularity::default(),
             savefile_path,
+            modes_dir,
             loadfile_result: Ok(()),
+            bot_cmd,
+            replay_import,
         };
 
         let mut new = Self {
@@ -803,6 +1580,15 @@ impl<T: Write> Application<T> {
 
         if custom_start_board.is_some() {
             new.settings.newgame.custom_encoded_board = custom_start_board;
+            new.settings.newgame.custom_encoded_puzzle_stages = None;
+        }
+
+        if let Some(fumen_import) = custom_start_fumen {
+            new.settings.newgame.load_fumen(fumen_import);
+        }
+
+        if custom_piece_sequence.is_some() {
+            new.settings.newgame.custom_piece_sequence = custom_piece_sequence;
         }
 
         if custom_start_seed.is_some() {
@@ -812,12 +1598,76 @@ impl<T: Write> Application<T> {
         new
     }
 
+    /// Check whether all built-in and loaded puzzle stages can be solved, returning a report and whether they all can.
+    pub fn check_puzzles(&self) -> (String, bool) {
+        let rotation_system = self.settings.gameplay().rotsys;
+
+        // Collect the stages of the built-in puzzle and those loaded from fumen, custom presets and mode files.
+        let mut puzzles = vec![(GameMode::TITLE_PUZZLE.to_owned(), Vec::new())];
+        if let Some(stages) = &self.settings.newgame.custom_encoded_puzzle_stages {
+            puzzles.push(("Custom".to_owned(), stages.clone()));
+        }
+        for (name, preset) in &self.settings.custom_game_slotmachine.slots {
+            if let Some(stages) = &preset.encoded_puzzle_stages {
+                puzzles.push((name.clone(), stages.clone()));
+            }
+        }
+        for mode_file in game_modes::load_mode_files(&self.temp_data.modes_dir)
+            .into_iter()
+            .flatten()
+        {
+            for (mod_id, mod_args) in &mode_file.mod_ids_args {
+                if mod_id != game_modifiers::Puzzle::MOD_ID {
+                    continue;
+                }
+                // Mode files with the built-in stages need no extra check.
+                if let Ok(Some(stages)) = serde_json::from_value::<
+                    Option<Vec<(String, Vec<Tetromino>)>>,
+                >(mod_args.clone())
+                {
+                    puzzles.push((mode_file.title.clone(), stages));
+                }
+            }
+        }
+
+        let mut report = vec![format!(
+            "Checking puzzles with {rotation_system:?} rotation:"
+        )];
+        let mut all_solvable = true;
+        for (puzzle_name, custom_stages) in puzzles {
+            for (stage_name, board, tetrominos) in game_modifiers::Puzzle::stages(&custom_stages) {
+                let pieces = tetrominos
+                    .iter()
+                    .map(|tet| tet.piece_spawn_state())
+                    .collect::<Vec<_>>();
+                let result =
+                    if crate::solver::solve_puzzle(&board, &pieces, &rotation_system).is_some() {
+                        "solvable"
+                    } else {
+                        all_solvable = false;
+                        "NOT SOLVABLE"
+                    };
+                report.push(format!("{puzzle_name} / {stage_name}: {result}"));
+            }
+        }
+
+        (report.join("\n"), all_solvable)
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
         // Console prologue: Initialization.
         // FIXME: Handle io::Error? If not, why not?
         let _e = self.initialize_terminal_state();
 
         let mut menu_stack = vec![Menu::Title];
+        if let Some((game_restoration_data, game_meta_data)) = self.temp_data.replay_import.take() {
+            menu_stack.push(Menu::TasEditor {
+                game_restoration_data: Box::new(game_restoration_data),
+                game_meta_data,
+                cursor_pos: 0,
+                unsaved_edits: true,
+            });
+        }
         loop {
             // Retrieve active menu, stop application if stack is empty.
             let Some(menu) = menu_stack.last_mut() else {
@@ -832,10 +1682,15 @@ impl<T: Write> Application<T> {
                     game_input_history,
                     game_meta_data,
                     game_renderer,
-                } => {
-                    self.run_menu_play_game(game, game_input_history, game_meta_data, game_renderer)
-                }
-                Menu::Pause => self.run_menu_pause(),
+                    split_recorder,
+                } => self.run_menu_play_game(
+                    game,
+                    game_input_history,
+                    game_meta_data,
+                    game_renderer,
+                    split_recorder,
+                ),
+                Menu::Pause { board_fumen } => self.run_menu_pause(board_fumen.clone()),
                 Menu::Settings => self.run_menu_settings(),
                 Menu::AdjustGraphics => self.run_menu_adjust_graphics(),
                 Menu::AdjustKeybinds => self.run_menu_adjust_keybinds(),
@@ -858,6 +1713,23 @@ impl<T: Write> Application<T> {
                     *replay_length,
                     game_renderer.as_mut(),
                 ),
+                Menu::TasEditor {
+                    game_restoration_data,
+                    game_meta_data,
+                    cursor_pos,
+                    unsaved_edits,
+                } => self.run_menu_tas_editor(
+                    game_restoration_data,
+                    game_meta_data,
+                    cursor_pos,
+                    unsaved_edits,
+                ),
+                Menu::Savepoints => self.run_menu_savepoints(),
+                Menu::DailyResults {
+                    cursor_pos,
+                    camera_pos,
+                } => self.run_menu_daily_results(cursor_pos, camera_pos),
+                Menu::CompareReplays { replays } => self.run_menu_compare_replays(replays),
                 Menu::Statistics => self.run_menu_statistics(),
                 Menu::About => self.run_menu_about(),
                 Menu::Quit => break,
@@ -976,3 +1848,74 @@ impl<T: Write> Application<T> {
         self.deinitialize_terminal_state()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn base64(bytes: &[u8]) -> String {
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes)
+    }
+
+    #[test]
+    fn inputbuf_round_trips() {
+        let inputbuf = vec![0, 1, 0x7f, 0x80, 300, u128::from(u64::MAX), u128::MAX];
+
+        let encoded = InputBufDummyType::encode(&inputbuf);
+
+        assert_eq!(InputBufDummyType::decode(&encoded), Ok(inputbuf));
+    }
+
+    #[test]
+    fn input_history_round_trips_with_large_time_deltas() {
+        let input_history = vec![
+            (Duration::ZERO, Input::Activate(Button::MoveLeft)),
+            (
+                Duration::from_millis(10),
+                Input::Deactivate(Button::MoveLeft),
+            ),
+            (
+                Duration::from_secs(60 * 60 * 24 * 365),
+                Input::Activate(Button::DropHard),
+            ),
+            (
+                Duration::from_millis(u64::MAX),
+                Input::Deactivate(Button::DropHard),
+            ),
+        ];
+        let compressed = CompressedInputHistory::new(&input_history);
+
+        let json = serde_json::to_string(&compressed).unwrap();
+        let deserialized: CompressedInputHistory = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.decompress(), input_history);
+    }
+
+    #[test]
+    fn inputbuf_reads_legacy_list_of_numbers() {
+        let deserialized: CompressedInputHistory =
+            serde_json::from_str(r#"{"inputbuf":[1,2,300]}"#).unwrap();
+
+        assert_eq!(deserialized.inputbuf, vec![1, 2, 300]);
+    }
+
+    #[test]
+    fn inputbuf_rejects_invalid_input() {
+        // Not base64.
+        assert!(InputBufDummyType::decode("not base64!").is_err());
+        // Last value has its continuation bit set.
+        assert!(InputBufDummyType::decode(&base64(&[0x01, 0x80])).is_err());
+        // Truncated encoding of a value.
+        let mut encoded = InputBufDummyType::encode(&[u128::MAX]);
+        encoded.truncate(encoded.len() - 4);
+        assert!(InputBufDummyType::decode(&encoded).is_err());
+        // Value with more than 128 bits.
+        let mut too_long = vec![0xff; 18];
+        too_long.push(0x7f);
+        assert!(InputBufDummyType::decode(&base64(&too_long)).is_err());
+        // Error is passed on when deserializing.
+        assert!(serde_json::from_str::<CompressedInputHistory>(r#"{"inputbuf":"gA=="}"#).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use falling_tetromino_engine::{Game, InGameTime, NotificationFeed, Phase, UpdateGameError};
+
+use crate::{
+    application::replay_text::encode_replay_text,
+    application::{
+        Application, CompressedInputHistory, GameMetaData, GameRestorationData, NewGameSettings,
+        ScoreEntry, Settings, UncompressedInputHistory,
+    },
+    fmt_helpers::fmt_duration,
+    game_renderers::{DiffPrintRenderer, Renderer},
+    palette::color_to_rgb,
+};
+
+/// Re-simulate a replay from the start, and call `on_frame` with the game at every frame (in-game time step).
+pub fn simulate_replay_frames(
+    game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+    replay_length: InGameTime,
+    frame_interval: Duration,
+    mut on_frame: impl FnMut(&Game, NotificationFeed) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut game = game_restoration_data.restore(0);
+    let mut inputs_loaded = 0usize;
+    let mut frame_time = game.state().time;
+
+    loop {
+        let mut feed = NotificationFeed::new();
+
+        let mut update_target_time = frame_time;
+        let mut do_forfeit = false;
+
+        if let Some(forfeit_time) = game_restoration_data.forfeit {
+            if forfeit_time <= update_target_time {
+                update_target_time = forfeit_time;
+                do_forfeit = true;
+            }
+        }
+
+        // Load all inputs up until the frame.
+        game_restoration_data.resimulate(
+            &mut game,
+            &mut inputs_loaded,
+            game_restoration_data.input_history.len(),
+            update_target_time,
+            |_game, _input_idx, msgs| feed.extend(msgs),
+        );
+
+        if do_forfeit {
+            match game.forfeit() {
+                Ok(msgs) => feed.extend(msgs),
+                Err(UpdateGameError::AlreadyEnded | UpdateGameError::TargetTimeInPast) => {}
+            }
+        }
+
+        on_frame(&game, feed)?;
+
+        if game.has_ended() || replay_length <= frame_time {
+            break Ok(());
+        }
+
+        frame_time += frame_interval;
+    }
+}
+
+fn export_file_path(game_meta_data: &GameMetaData, extension: &str) -> PathBuf {
+    // Keep filenames portable.
+    let sanitize = |s: &str| {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+    };
+    PathBuf::from(format!(
+        "tetro-tui_replay_{}_{}.{extension}",
+        sanitize(&game_meta_data.title),
+        sanitize(&game_meta_data.datetime),
+    ))
+}
+
+/// A finished game together with everything needed to re-simulate it, so others can verify its result.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VerifiableReplay {
+    version: String,
+    score_entry: ScoreEntry,
+    game_restoration_data: GameRestorationData<CompressedInputHistory>,
+}
+
+/// Re-simulate an exported replay file and check that it leads to the result it claims.
+pub fn verify_replay_file(path: &Path) -> io::Result<String> {
+    let VerifiableReplay {
+        version,
+        score_entry,
+        game_restoration_data,
+    } = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    let game_restoration_data =
+        game_restoration_data.map(|input_history| input_history.decompress());
+
+    // Jump straight to the end of the game.
+    let mut end_result = None;
+    simulate_replay_frames(
+        &game_restoration_data,
+        score_entry.time_elapsed,
+        score_entry.time_elapsed.max(Duration::from_millis(1)),
+        |game, _feed| {
+            end_result = Some((
+                match game.phase() {
+                    Phase::GameEnd { is_win, .. } => Some(*is_win),
+                    _ => None,
+                },
+                game.state().time,
+                game.state().lineclears,
+                game.state().points,
+                game.state().pieces_locked,
+            ));
+            Ok(())
+        },
+    )?;
+
+    let claimed_result = Some((
+        Some(score_entry.is_win),
+        score_entry.time_elapsed,
+        score_entry.lineclears,
+        score_entry.points_scored,
+        score_entry.pieces_locked,
+    ));
+
+    let game_meta_data = &score_entry.game_meta_data;
+    if end_result == claimed_result {
+        Ok(format!(
+            "Verified: {} ({}) played on v{version}, {} in {}.",
+            game_meta_data.title,
+            game_meta_data.datetime,
+            if score_entry.is_win {
+                "completed"
+            } else {
+                "ended"
+            },
+            fmt_duration(score_entry.time_elapsed),
+        ))
+    } else {
+        Err(io::Error::other(format!(
+            "Replay of {} ({}) does not reproduce its claimed result.",
+            game_meta_data.title, game_meta_data.datetime,
+        )))
+    }
+}
+
+type SvgFrame = Vec<(usize, usize, (u8, u8, u8))>;
+
+/// Collect the colored cells of the visible part of the board, including the piece in play.
+fn svg_frame(game: &Game, settings: &Settings, h_visible: usize) -> SvgFrame {
+    const FALLBACK_RGB: (u8, u8, u8) = (192, 192, 192);
+
+    let mut cells = SvgFrame::new();
+
+    for (y, line) in game.state().board.iter().take(h_visible).enumerate() {
+        for (x, tile) in line.iter().enumerate() {
+            if let Some(tile_id) = tile {
+                let rgb = settings
+                    .palette_lockedtiles()
+                    .get(tile_id)
+                    .and_then(|color| color_to_rgb(*color))
+                    .unwrap_or(FALLBACK_RGB);
+                cells.push((x, y, rgb));
+            }
+        }
+    }
+
+    if let Phase::PieceInPlay { piece, .. } = game.phase() {
+        for ((x, y), tile_id) in piece.tiles() {
+            if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                if y < h_visible {
+                    let rgb = settings
+                        .palette()
+                        .get(&tile_id)
+                        .and_then(|color| color_to_rgb(*color))
+                        .unwrap_or(FALLBACK_RGB);
+                    cells.push((x, y, rgb));
+                }
+            }
+        }
+    }
+
+    cells
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<T: Write> Application<T> {
+    /// Export a finished game with its replay as JSON file, which can be checked with `--verify-replay`.
+    pub(in crate::application) fn export_replay_verifiable(
+        score_entry: &ScoreEntry,
+        game_restoration_data: &GameRestorationData<CompressedInputHistory>,
+    ) -> io::Result<PathBuf> {
+        let verifiable_replay = VerifiableReplay {
+            version: crate::CRATE_VERSION.to_owned(),
+            score_entry: score_entry.clone(),
+            game_restoration_data: game_restoration_data.clone(),
+        };
+
+        let path = export_file_path(&score_entry.game_meta_data, "json");
+        fs::write(&path, serde_json::to_string(&verifiable_replay)?)?;
+
+        Ok(path)
+    }
+
+    /// Export a replay in the human-readable text format, which can be opened with `--import-replay`.
+    pub(in crate::application) fn export_replay_text(
+        game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+        game_meta_data: &GameMetaData,
+    ) -> io::Result<PathBuf> {
+        let path = export_file_path(game_meta_data, "replay.txt");
+        fs::write(
+            &path,
+            encode_replay_text(game_restoration_data, game_meta_data),
+        )?;
+
+        Ok(path)
+    }
+
+    /// Export the board of a replay frame (with the piece in play) as fumen string to a text file.
+    pub(in crate::application) fn export_board_fumen(
+        game: &Game,
+        game_meta_data: &GameMetaData,
+    ) -> io::Result<PathBuf> {
+        let fumen = NewGameSettings::encode_fumen(&game.state().board, game.phase().piece());
+
+        let path = export_file_path(game_meta_data, "txt");
+        fs::write(&path, fumen + "\n")?;
+
+        Ok(path)
+    }
+
+    /// Export a replay as asciicast v2 file (<https://docs.asciinema.org/manual/asciicast/v2/>),
+    /// consisting of the exact frames the default renderer would output at the currently set framerate.
+    pub(in crate::application) fn export_replay_asciicast(
+        &self,
+        game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+        game_meta_data: &GameMetaData,
+        replay_length: InGameTime,
+    ) -> io::Result<PathBuf> {
+        let frame_interval = Duration::from_secs_f64(self.settings.graphics().game_fps.recip());
+
+        let header = serde_json::json!({
+            "version": 2,
+            "width": Self::W_MAIN,
+            // The Elektronika glyphset uses one row more than the others.
+            "height": Self::H_MAIN + 1,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "title": format!("Tetro TUI - {}", game_meta_data.title),
+            "env": { "TERM": "xterm-256color" },
+        });
+
+        let mut cast = format!("{header}\n");
+
+        // The renderer draws into this in-memory sink instead of the terminal.
+        let mut sink = Vec::<u8>::new();
+        let mut renderer = DiffPrintRenderer::default();
+        renderer.set_render_offset(0, 0);
+
+        simulate_replay_frames(
+            game_restoration_data,
+            replay_length,
+            frame_interval,
+            |game, feed| {
+                renderer.push_game_notification_feed(feed);
+                renderer.render(
+                    &mut sink,
+                    game,
+                    game_meta_data,
+                    &self.settings,
+                    &self.temp_data,
+                    &Vec::new(),
+                    Some((replay_length, 1.0)),
+                )?;
+
+                if !sink.is_empty() {
+                    let event = (
+                        game.state().time.as_secs_f64(),
+                        "o",
+                        String::from_utf8_lossy(&sink),
+                    );
+                    // Writing into a `String` cannot fail.
+                    let _ = writeln!(cast, "{}", serde_json::to_string(&event)?);
+                    sink.clear();
+                }
+
+                Ok(())
+            },
+        )?;
+
+        let path = export_file_path(game_meta_data, "cast");
+        fs::write(&path, cast)?;
+
+        Ok(path)
+    }
+
+    /// Export a replay as animated SVG image of the board, colored with the active palette.
+    ///
+    /// This re-simulates the game at the currently set framerate, but only stores frames where the board changed.
+    pub(in crate::application) fn export_replay_svg(
+        &self,
+        game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+        game_meta_data: &GameMetaData,
+        replay_length: InGameTime,
+    ) -> io::Result<PathBuf> {
+        const CELL_SIZE: usize = 16;
+        // Show a little above the lock out height, where pieces spawn.
+        const H_VISIBLE: usize = Game::LOCK_OUT_HEIGHT + 2;
+
+        let frame_interval = Duration::from_secs_f64(self.settings.graphics().game_fps.recip());
+
+        let mut frames: Vec<(InGameTime, SvgFrame)> = Vec::new();
+
+        simulate_replay_frames(
+            game_restoration_data,
+            replay_length,
+            frame_interval,
+            |game, _feed| {
+                let frame = svg_frame(game, &self.settings, H_VISIBLE);
+                if frames
+                    .last()
+                    .is_none_or(|(_, last_frame)| *last_frame != frame)
+                {
+                    frames.push((game.state().time, frame));
+                }
+                Ok(())
+            },
+        )?;
+
+        let (w_svg, h_svg) = (Game::WIDTH * CELL_SIZE, H_VISIBLE * CELL_SIZE);
+
+        let mut svg = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w_svg}" height="{h_svg}" viewBox="0 0 {w_svg} {h_svg}">"#
+        );
+        let _ = writeln!(
+            svg,
+            "<title>Tetro TUI - {}</title>",
+            escape_xml(&game_meta_data.title)
+        );
+        let _ = writeln!(
+            svg,
+            r##"<rect width="100%" height="100%" fill="#101010"/>"##
+        );
+
+        for (i, (frame_time, cells)) in frames.iter().enumerate() {
+            // Each frame is only visible until the next one starts; the last one stays.
+            let timing = match frames.get(i + 1) {
+                Some((next_frame_time, _)) => format!(
+                    r#"dur="{:.3}s""#,
+                    next_frame_time.saturating_sub(*frame_time).as_secs_f64()
+                ),
+                None => r#"fill="freeze""#.to_owned(),
+            };
+            let _ = writeln!(
+                svg,
+                r#"<g visibility="hidden"><set attributeName="visibility" to="visible" begin="{:.3}s" {timing}/>"#,
+                frame_time.as_secs_f64()
+            );
+            for (x, y, (r, g, b)) in cells {
+                let _ = writeln!(
+                    svg,
+                    r##"<rect x="{}" y="{}" width="{CELL_SIZE}" height="{CELL_SIZE}" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+                    x * CELL_SIZE,
+                    (H_VISIBLE - 1 - y) * CELL_SIZE,
+                );
+            }
+            let _ = writeln!(svg, "</g>");
+        }
+
+        let _ = writeln!(svg, "</svg>");
+
+        let path = export_file_path(game_meta_data, "svg");
+        fs::write(&path, svg)?;
+
+        Ok(path)
+    }
+}
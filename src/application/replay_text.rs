@@ -0,0 +1,271 @@
+//! Human-readable text format of replays, which can be diffed and edited by hand.
+//!
+//! ```text
+//! # tetro-tui replay v0.0.0
+//! title: Puzzle
+//! datetime: 2026-10-18_12:00
+//! stat: [{"PiecesLocked":0},false]
+//! seed: 42
+//! builder: {"tetromino_generator":...,"config":...}
+//! mod: ["Puzzle",""]
+//! forfeit: 5230.416667ms
+//!
+//! 1234ms +MoveLeft
+//! 1290ms -MoveLeft
+//! ```
+//!
+//! Header lines are `key: value`, followed by one line per input with its in-game time in milliseconds
+//! (with fraction if not whole, so times round-trip exactly) and whether the button is pressed (`+`) or released (`-`). Empty lines and lines starting with `#` are ignored.
+
+use std::time::Duration;
+
+use falling_tetromino_engine::{Button, GameBuilder, Input};
+
+use crate::application::{GameMetaData, GameRestorationData, UncompressedInputHistory};
+
+/// Encode a replay in the text format.
+pub fn encode_replay_text(
+    game_restoration_data: &GameRestorationData<UncompressedInputHistory>,
+    game_meta_data: &GameMetaData,
+) -> String {
+    let mut lines = vec![
+        format!("# tetro-tui replay v{}", crate::CRATE_VERSION),
+        format!("title: {}", game_meta_data.title),
+        format!("datetime: {}", game_meta_data.datetime),
+        format!(
+            "stat: {}",
+            serde_json::to_string(&game_meta_data.comparison_stat).unwrap()
+        ),
+    ];
+
+    // The seed is pulled out of the builder so it can be read (and changed) at a glance.
+    let mut builder = serde_json::to_value(&game_restoration_data.builder).unwrap();
+    if let Some(seed) = builder
+        .as_object_mut()
+        .and_then(|fields| fields.remove("seed"))
+        .filter(|seed| !seed.is_null())
+    {
+        lines.push(format!("seed: {seed}"));
+    }
+    lines.push(format!("builder: {builder}"));
+
+    for mod_id_args in &game_restoration_data.mod_ids_args {
+        lines.push(format!(
+            "mod: {}",
+            serde_json::to_string(mod_id_args).unwrap()
+        ));
+    }
+
+    if let Some(forfeit_time) = game_restoration_data.forfeit {
+        lines.push(format!("forfeit: {}", fmt_millis(forfeit_time)));
+    }
+
+    lines.push(String::new());
+
+    for (update_time, input) in &game_restoration_data.input_history {
+        let (sign, button) = match input {
+            Input::Activate(button) => ('+', button),
+            Input::Deactivate(button) => ('-', button),
+        };
+        lines.push(format!("{} {sign}{button:?}", fmt_millis(*update_time)));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Decode a replay from the text format.
+///
+/// The returned metadata only contains what the header states; it is marked as tool-assisted since the inputs may have been edited.
+pub fn decode_replay_text(
+    text: &str,
+) -> Result<(GameRestorationData<UncompressedInputHistory>, GameMetaData), String> {
+    let mut title = None;
+    let mut datetime = String::new();
+    let mut comparison_stat = None;
+    let mut seed = None;
+    let mut builder = None;
+    let mut mod_ids_args = Vec::new();
+    let mut forfeit = None;
+    let mut input_history = UncompressedInputHistory::new();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let err = |msg: String| format!("line {}: {msg}", line_idx + 1);
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(": ") {
+            if !input_history.is_empty() {
+                return Err(err(format!("header line {key:?} after inputs")));
+            }
+            let value = value.trim();
+            match key {
+                "title" => title = Some(value.to_owned()),
+                "datetime" => datetime = value.to_owned(),
+                "stat" => {
+                    comparison_stat =
+                        Some(serde_json::from_str(value).map_err(|e| err(e.to_string()))?);
+                }
+                "seed" => {
+                    seed = Some(
+                        value
+                            .parse::<u64>()
+                            .map_err(|e| err(format!("invalid seed: {e}")))?,
+                    );
+                }
+                "builder" => {
+                    builder = Some(
+                        serde_json::from_str::<serde_json::Value>(value)
+                            .map_err(|e| err(e.to_string()))?,
+                    );
+                }
+                "mod" => {
+                    let mod_id_args =
+                        serde_json::from_str(value).map_err(|e| err(e.to_string()))?;
+                    mod_ids_args.push(mod_id_args);
+                }
+                "forfeit" => forfeit = Some(parse_millis(value).map_err(err)?),
+                _ => return Err(err(format!("unknown header {key:?}"))),
+            }
+            continue;
+        }
+
+        let (time, input) = line.split_once(' ').ok_or_else(|| {
+            err(format!(
+                "expected input like `1234ms +MoveLeft`, got {line:?}"
+            ))
+        })?;
+        let update_time = parse_millis(time).map_err(err)?;
+        if input_history
+            .last()
+            .is_some_and(|(last_update_time, _)| *last_update_time > update_time)
+        {
+            return Err(err("inputs must be in chronological order".to_owned()));
+        }
+        let input = input.trim();
+        let activation = match input.chars().next() {
+            Some('+') => Input::Activate,
+            Some('-') => Input::Deactivate,
+            _ => {
+                return Err(err(format!(
+                    "expected `+` or `-` before button, got {input:?}"
+                )))
+            }
+        };
+        let button = Button::VARIANTS
+            .into_iter()
+            .find(|button| format!("{button:?}") == input[1..])
+            .ok_or_else(|| err(format!("unknown button {:?}", &input[1..])))?;
+        input_history.push((update_time, activation(button)));
+    }
+
+    let mut builder = builder.ok_or("missing header \"builder\"")?;
+    if let Some(seed) = seed {
+        builder
+            .as_object_mut()
+            .ok_or("header \"builder\" must be a JSON object")?
+            .insert("seed".to_owned(), seed.into());
+    }
+    let builder: GameBuilder =
+        serde_json::from_value(builder).map_err(|e| format!("invalid builder: {e}"))?;
+
+    let game_restoration_data = GameRestorationData {
+        builder,
+        mod_ids_args,
+        input_history,
+        forfeit,
+    };
+
+    let game_meta_data = GameMetaData {
+        datetime,
+        title: title.ok_or("missing header \"title\"")?,
+        comparison_stat: comparison_stat.ok_or("missing header \"stat\"")?,
+        forked_from: None,
+        used_undo: false,
+        official_daily: false,
+        tool_assisted: true,
+        bot_played: false,
+        used_solver: false,
+        puzzle: false,
+    };
+
+    Ok((game_restoration_data, game_meta_data))
+}
+
+const NANOS_PER_MILLI: u32 = 1_000_000;
+
+fn fmt_millis(time: Duration) -> String {
+    let sub_millis = time.subsec_nanos() % NANOS_PER_MILLI;
+    if sub_millis == 0 {
+        format!("{}ms", time.as_millis())
+    } else {
+        format!("{}.{sub_millis:06}ms", time.as_millis())
+    }
+}
+
+fn parse_millis(s: &str) -> Result<Duration, String> {
+    let err = || format!("expected time like `1234ms` or `1234.5ms`, got {s:?}");
+
+    let millis = s.strip_suffix("ms").ok_or_else(err)?;
+    let (whole, fraction) = millis.split_once('.').unwrap_or((millis, ""));
+    if fraction.len() > 6 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(err());
+    }
+
+    let whole = whole.parse::<u64>().map_err(|_| err())?;
+    let sub_millis = format!("{fraction:0<6}").parse::<u32>().unwrap();
+
+    Ok(Duration::from_millis(whole) + Duration::from_nanos(sub_millis.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use falling_tetromino_engine::Stat;
+
+    use super::*;
+
+    #[test]
+    fn replay_text_round_trips() {
+        let game = GameBuilder::new().seed(42).build();
+        let input_history = vec![
+            (Duration::from_millis(10), Input::Activate(Button::MoveLeft)),
+            (
+                Duration::from_millis(10),
+                Input::Deactivate(Button::MoveLeft),
+            ),
+            (
+                Duration::from_nanos(1_234_500_001),
+                Input::Activate(Button::DropHard),
+            ),
+        ];
+        let mut game_restoration_data = GameRestorationData::new(
+            &game,
+            input_history,
+            Some(Duration::from_nanos(5_230_416_667)),
+        );
+        game_restoration_data
+            .mod_ids_args
+            .push(("Puzzle".to_owned(), "[\"a: \\\"b\\\"\\nc\"]".to_owned()));
+        let game_meta_data = GameMetaData {
+            datetime: "2026-10-18_12:00".to_owned(),
+            title: "Puzzle: Custom".to_owned(),
+            comparison_stat: (Stat::PiecesLocked(3), true),
+            forked_from: None,
+            used_undo: false,
+            official_daily: false,
+            tool_assisted: true,
+            bot_played: false,
+            used_solver: false,
+            puzzle: false,
+        };
+
+        let text = encode_replay_text(&game_restoration_data, &game_meta_data);
+        let (decoded_restoration_data, decoded_meta_data) = decode_replay_text(&text).unwrap();
+
+        assert_eq!(decoded_restoration_data, game_restoration_data);
+        assert_eq!(decoded_meta_data, game_meta_data);
+    }
+}
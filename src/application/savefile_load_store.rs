@@ -51,6 +51,8 @@ impl<T: Write> Application<T> {
             compressed_game_saves,
         ) = serde_json::from_str(&save_str)?;
 
+        settings.newgame.migrate_legacy_fields();
+
         *game_saves = (
             compressed_game_saves.0,
             compressed_game_saves
@@ -67,9 +69,15 @@ impl<T: Write> Application<T> {
         if self.temp_data.save_on_exit < SavefileGranularity::RememberSettingsScores {
             // Clear scoreboard if no game data is wished to be stored.
             self.scores_and_replays.entries.clear();
+            self.scores_and_replays.daily_entries.clear();
         } else if self.temp_data.save_on_exit < SavefileGranularity::RememberSettingsScoresReplays {
             // Clear past game inputs if no game input data is wished to be stored.
-            for (_entry, restoration_data) in &mut self.scores_and_replays.entries {
+            for (_entry, restoration_data) in self
+                .scores_and_replays
+                .entries
+                .iter_mut()
+                .chain(&mut self.scores_and_replays.daily_entries)
+            {
                 restoration_data.take();
             }
         }
@@ -0,0 +1,648 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use falling_tetromino_engine::{
+    Board, Button, Game, Notification, NotificationFeed, Orientation, Phase, Piece, RotationSystem,
+    Tetromino,
+};
+
+use crate::fmt_helpers::FmtTetromino;
+
+/* # Bot protocol
+
+An external bot is a child process spoken to over stdin/stdout, one JSON object per line.
+The protocol is modeled after the community "Tetris Bot Protocol" (TBP):
+
+  frontend -> bot: `rules`, `start`, `new_piece`, `suggest`, `play`, `stop`, `quit`.
+  bot -> frontend: `info`, `ready`, `suggestion`, `error`.
+
+Handshake: bot sends `info`, we answer `rules`, bot answers `ready`, then we `start` a game.
+
+Differences to TBP:
+- The board sent with `start` has `Game::HEIGHT` rows (bottom row first), cells are `null` or a piece letter/"G".
+- A piece `location` uses the engine's coordinates: `x`,`y` is the bottom-left corner of the piece's
+  bounding box (with `y` counting upwards from the bottom), *not* the SRS rotation center.
+- Orientations are "north", "east", "south", "west" (the rotation system is whatever the player has set).
+*/
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BotOrientation {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl From<Orientation> for BotOrientation {
+    fn from(orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::N => BotOrientation::North,
+            Orientation::E => BotOrientation::East,
+            Orientation::S => BotOrientation::South,
+            Orientation::W => BotOrientation::West,
+        }
+    }
+}
+
+impl From<BotOrientation> for Orientation {
+    fn from(orientation: BotOrientation) -> Self {
+        match orientation {
+            BotOrientation::North => Orientation::N,
+            BotOrientation::East => Orientation::E,
+            BotOrientation::South => Orientation::S,
+            BotOrientation::West => Orientation::W,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BotSpin {
+    #[default]
+    None,
+    Mini,
+    Full,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BotPieceLocation {
+    #[serde(rename = "type")]
+    pub tetromino: Tetromino,
+    pub orientation: BotOrientation,
+    pub x: isize,
+    pub y: isize,
+}
+
+impl BotPieceLocation {
+    pub fn piece(&self) -> Piece {
+        Piece {
+            tetromino: self.tetromino,
+            orientation: self.orientation.into(),
+            position: (self.x, self.y),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BotMove {
+    pub location: BotPieceLocation,
+    #[serde(default)]
+    pub spin: BotSpin,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FrontendMessage {
+    Rules {
+        randomizer: String,
+    },
+    Start {
+        hold: Option<Tetromino>,
+        queue: Vec<Tetromino>,
+        combo: u32,
+        back_to_back: bool,
+        board: Vec<Vec<Option<&'static str>>>,
+    },
+    NewPiece {
+        piece: Tetromino,
+    },
+    Suggest,
+    Play {
+        #[serde(rename = "move")]
+        mv: BotMove,
+    },
+    Stop,
+    Quit,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotMessage {
+    Info {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        version: String,
+        #[serde(default)]
+        author: String,
+    },
+    Ready,
+    Error {
+        #[serde(default)]
+        reason: String,
+    },
+    Suggestion {
+        moves: Vec<BotMove>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum BotStatus {
+    AwaitingInfo,
+    AwaitingReady,
+    Ready,
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub struct BotInterface {
+    child: Child,
+    stdin: ChildStdin,
+    receiver: Receiver<Result<BotMessage, String>>,
+    status: BotStatus,
+    started: bool,
+    needs_start: bool,
+    // What the bot believes the queue (current piece first) and hold to be.
+    known_queue: VecDeque<Tetromino>,
+    known_hold: Option<Tetromino>,
+    awaiting_suggestion: bool,
+    stale_suggestions: usize,
+    suggestion: Option<Vec<BotMove>>,
+    expected_lock: Option<Piece>,
+}
+
+impl BotInterface {
+    pub fn spawn(bot_cmd: &str) -> io::Result<Self> {
+        let words =
+            split_command(bot_cmd).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let Some((program, args)) = words.split_first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty bot command",
+            ));
+        };
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        // Reader thread: parse lines and hand them over; exits when the bot closes its stdout.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let message = serde_json::from_str(&line).map_err(|e| e.to_string());
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            receiver,
+            status: BotStatus::AwaitingInfo,
+            started: false,
+            needs_start: true,
+            known_queue: VecDeque::new(),
+            known_hold: None,
+            awaiting_suggestion: false,
+            stale_suggestions: 0,
+            suggestion: None,
+            expected_lock: None,
+        })
+    }
+
+    fn send(&mut self, message: &FrontendMessage) -> Result<(), String> {
+        let line = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        writeln!(self.stdin, "{line}")
+            .and_then(|()| self.stdin.flush())
+            .map_err(|e| format!("Bot unreachable: {e}"))
+    }
+
+    fn fail(&mut self, reason: String) -> String {
+        self.status = BotStatus::Failed(reason.clone());
+        reason
+    }
+
+    /// Make the bot start over from the current game state at the next poll.
+    pub fn restart(&mut self) {
+        if self.awaiting_suggestion {
+            self.awaiting_suggestion = false;
+            self.stale_suggestions += 1;
+        }
+        self.suggestion = None;
+        self.expected_lock = None;
+        self.needs_start = true;
+    }
+
+    /// Inform the bot interface of what happened in-game, to detect if the bot's view got out of sync.
+    pub fn observe_feed(&mut self, feed: &NotificationFeed) {
+        for (notification, _) in feed {
+            if let Notification::PieceLocked { piece } = notification {
+                if self
+                    .expected_lock
+                    .take()
+                    .is_none_or(|expected| !same_tiles(&expected, piece))
+                {
+                    self.restart();
+                }
+            }
+        }
+    }
+
+    /// Communicate with the bot and return which buttons should be pressed (and released) in order next.
+    ///
+    /// Also returns messages that could be shown to the player.
+    pub fn poll(&mut self, game: &Game) -> (Vec<Button>, Vec<String>) {
+        let mut msgs = Vec::new();
+
+        if matches!(self.status, BotStatus::Failed(_)) {
+            return (Vec::new(), msgs);
+        }
+
+        // 1. Process everything the bot said since the last poll.
+        loop {
+            let message = match self.receiver.try_recv() {
+                Ok(Ok(message)) => message,
+                Ok(Err(e)) => {
+                    msgs.push(format!("Bot sent invalid message: {e}"));
+                    continue;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    msgs.push(self.fail("Bot process exited".to_owned()));
+                    return (Vec::new(), msgs);
+                }
+            };
+
+            match message {
+                BotMessage::Info {
+                    name,
+                    version,
+                    author,
+                } => {
+                    if self.status == BotStatus::AwaitingInfo {
+                        msgs.push(format!("Bot: {name} {version} by {author}"));
+                        let rules = FrontendMessage::Rules {
+                            randomizer: "unknown".to_owned(),
+                        };
+                        if let Err(e) = self.send(&rules) {
+                            msgs.push(self.fail(e));
+                            return (Vec::new(), msgs);
+                        }
+                        self.status = BotStatus::AwaitingReady;
+                    }
+                }
+                BotMessage::Ready => {
+                    if self.status == BotStatus::AwaitingReady {
+                        self.status = BotStatus::Ready;
+                    }
+                }
+                BotMessage::Error { reason } => {
+                    msgs.push(self.fail(format!("Bot error: {reason}")));
+                    return (Vec::new(), msgs);
+                }
+                BotMessage::Suggestion { moves } => {
+                    if self.stale_suggestions > 0 {
+                        // Answer to a question about a game state that is no longer relevant.
+                        self.stale_suggestions -= 1;
+                    } else if self.awaiting_suggestion {
+                        self.awaiting_suggestion = false;
+                        self.suggestion = Some(moves);
+                    }
+                }
+                BotMessage::Unknown => {}
+            }
+        }
+
+        if self.status != BotStatus::Ready {
+            return (Vec::new(), msgs);
+        }
+
+        // 2. We only act when there's a piece to be controlled.
+        let Phase::PieceInPlay { piece, .. } = game.phase() else {
+            return (Vec::new(), msgs);
+        };
+
+        let state = game.state();
+        let actual_queue = std::iter::once(piece.tetromino)
+            .chain(state.piece_preview.iter().copied())
+            .collect::<VecDeque<_>>();
+        let actual_hold = state.piece_held.map(|(tet, _)| tet);
+
+        // 3. Make sure the bot knows what the current game looks like.
+        if self.suggestion.is_none() && !self.awaiting_suggestion {
+            let in_sync = !self.needs_start
+                && self.known_hold == actual_hold
+                && actual_queue.len() >= self.known_queue.len()
+                && actual_queue
+                    .iter()
+                    .zip(&self.known_queue)
+                    .all(|(a, b)| a == b);
+
+            let result = if in_sync {
+                let new_pieces = actual_queue
+                    .iter()
+                    .skip(self.known_queue.len())
+                    .copied()
+                    .collect::<Vec<_>>();
+                new_pieces.into_iter().try_for_each(|piece| {
+                    self.known_queue.push_back(piece);
+                    self.send(&FrontendMessage::NewPiece { piece })
+                })
+            } else {
+                let start = FrontendMessage::Start {
+                    hold: actual_hold,
+                    queue: actual_queue.iter().copied().collect(),
+                    combo: state.consecutive_line_clears,
+                    back_to_back: false,
+                    board: encode_board_rows(&state.board),
+                };
+                self.known_queue = actual_queue;
+                self.known_hold = actual_hold;
+                self.needs_start = false;
+                if self.started {
+                    // Resynchronize by restarting the bot's game.
+                    self.send(&FrontendMessage::Stop)
+                        .and_then(|()| self.send(&start))
+                } else {
+                    self.started = true;
+                    self.send(&start)
+                }
+            };
+
+            if let Err(e) = result.and_then(|()| self.send(&FrontendMessage::Suggest)) {
+                msgs.push(self.fail(e));
+                return (Vec::new(), msgs);
+            }
+            self.awaiting_suggestion = true;
+
+            return (Vec::new(), msgs);
+        }
+
+        // 4. Turn the suggestion into button presses.
+        let Some(moves) = self.suggestion.clone() else {
+            return (Vec::new(), msgs);
+        };
+
+        for mv in moves {
+            let target = mv.location.piece();
+
+            if target.tetromino != piece.tetromino {
+                // The bot wants to use hold. We hold now and finish the move once the next piece has spawned.
+                let can_hold = matches!(state.piece_held, None | Some((_, true)));
+                let holds_into_target = match actual_hold {
+                    Some(held) => held == target.tetromino,
+                    None => actual_queue.get(1) == Some(&target.tetromino),
+                };
+                if can_hold && holds_into_target {
+                    return (vec![Button::HoldPiece], msgs);
+                }
+                continue;
+            }
+
+            let Some(mut buttons) =
+                find_path(*piece, &target, &state.board, &game.config.rotation_system)
+            else {
+                continue;
+            };
+            buttons.push(Button::DropHard);
+
+            // Update what the bot believes, as TBP prescribes after a `play`.
+            if self.known_queue.front() != Some(&target.tetromino) {
+                let front = self.known_queue.pop_front();
+                if self.known_hold.is_none() {
+                    self.known_queue.pop_front();
+                }
+                self.known_hold = front;
+            } else {
+                self.known_queue.pop_front();
+            }
+            self.expected_lock = Some(target);
+            self.suggestion = None;
+
+            if let Err(e) = self.send(&FrontendMessage::Play { mv }) {
+                msgs.push(self.fail(e));
+                return (Vec::new(), msgs);
+            }
+
+            return (buttons, msgs);
+        }
+
+        // None of the suggested moves are possible; Drop the piece and start over.
+        msgs.push("Bot suggested no reachable move".to_owned());
+        self.restart();
+
+        (vec![Button::DropHard], msgs)
+    }
+}
+
+impl Drop for BotInterface {
+    fn drop(&mut self) {
+        // Ask nicely first, then make sure.
+        let _ = self.send(&FrontendMessage::Quit);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Split a command into program and arguments the way a POSIX shell does,
+/// so e.g. paths containing spaces can be given in quotes.
+fn split_command(cmd: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = cmd.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            // Single quotes: Everything up to the closing quote is taken literally.
+            '\'' => {
+                let word = word.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote".to_owned()),
+                    }
+                }
+            }
+            // Double quotes: Backslashes only escape quotes and backslashes.
+            '"' => {
+                let word = word.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => word.extend(['\\', c]),
+                            None => return Err("unterminated double quote".to_owned()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated double quote".to_owned()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => word.get_or_insert_default().push(c),
+                None => return Err("trailing backslash".to_owned()),
+            },
+            c => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+
+    Ok(words)
+}
+
+/// Board rows with cells named by the piece letter of their tile, or `"G"` for garbage.
+fn encode_board_rows(board: &Board) -> Vec<Vec<Option<&'static str>>> {
+    board
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|tile| {
+                    tile.map(|tile_id| {
+                        Tetromino::VARIANTS
+                            .into_iter()
+                            .find(|tet| tet.tile_id() == tile_id)
+                            .map_or("G", |tet| tet.charstr_ascii())
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn same_tiles(piece_a: &Piece, piece_b: &Piece) -> bool {
+    let coords = |piece: &Piece| {
+        let mut coords = piece.tiles().map(|(coord, _)| coord);
+        coords.sort();
+        coords
+    };
+    piece_a.tetromino == piece_b.tetromino && coords(piece_a) == coords(piece_b)
+}
+
+/// Breadth-first search for the shortest sequence of button presses that moves a piece to the target.
+fn find_path(
+    start: Piece,
+    target: &Piece,
+    board: &Board,
+    rotation_system: &RotationSystem,
+) -> Option<Vec<Button>> {
+    let mut predecessors = HashMap::<Piece, (Piece, Button)>::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(piece) = queue.pop_front() {
+        if same_tiles(&piece, target) {
+            let mut buttons = Vec::new();
+            let mut current = piece;
+            while current != start {
+                let (previous, button) = predecessors[&current];
+                buttons.push(button);
+                current = previous;
+            }
+            buttons.reverse();
+            return Some(buttons);
+        }
+
+        let successors = [
+            (Button::MoveLeft, piece.offset_on(board, (-1, 0)).ok()),
+            (Button::MoveRight, piece.offset_on(board, (1, 0)).ok()),
+            (
+                Button::RotateLeft,
+                rotation_system.rotate(&piece, board, -1),
+            ),
+            (
+                Button::RotateRight,
+                rotation_system.rotate(&piece, board, 1),
+            ),
+            (Button::Rotate180, rotation_system.rotate(&piece, board, 2)),
+            // Soft dropping a grounded piece would lock it, so only allow this while airborne.
+            (Button::DropSoft, piece.offset_on(board, (0, -1)).ok()),
+            (Button::TeleDown, Some(piece.teleported(board, (0, -1)))),
+            (Button::TeleLeft, Some(piece.teleported(board, (-1, 0)))),
+            (Button::TeleRight, Some(piece.teleported(board, (1, 0)))),
+        ];
+
+        for (button, successor) in successors {
+            let Some(successor) = successor else {
+                continue;
+            };
+            if successor == start || predecessors.contains_key(&successor) {
+                continue;
+            }
+            predecessors.insert(successor, (piece, button));
+            queue.push_back(successor);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU8;
+
+    use super::*;
+
+    #[test]
+    fn splits_command_like_shell() {
+        let split = |cmd| split_command(cmd).unwrap();
+
+        assert_eq!(split("  python3   my_bot.py "), ["python3", "my_bot.py"]);
+        assert_eq!(
+            split(r#""/home/me/my bots/bot" --name 'cold clear' a\ b"#),
+            ["/home/me/my bots/bot", "--name", "cold clear", "a b"]
+        );
+        assert_eq!(
+            split(r#"bot "say \"hi\"" 'it''s' "C:\bots""#),
+            ["bot", r#"say "hi""#, "its", r"C:\bots"]
+        );
+        assert_eq!(split(r#"bot "" ''"#), ["bot", "", ""]);
+        assert!(split("").is_empty());
+
+        assert!(split_command("bot 'unterminated").is_err());
+        assert!(split_command(r#"bot "unterminated"#).is_err());
+        assert!(split_command(r"bot \").is_err());
+    }
+
+    #[test]
+    fn board_cells_are_named_by_piece_letter() {
+        let mut board = Board::default();
+        for (x, tet) in Tetromino::VARIANTS.into_iter().enumerate() {
+            board[0][x] = Some(tet.tile_id());
+        }
+        board[0][7] = NonZeroU8::new(254);
+
+        let rows = encode_board_rows(&board);
+
+        assert_eq!(
+            rows[0],
+            [
+                Some("O"),
+                Some("I"),
+                Some("S"),
+                Some("Z"),
+                Some("T"),
+                Some("L"),
+                Some("J"),
+                Some("G"),
+                None,
+                None
+            ]
+        );
+        assert!(rows[1..].iter().flatten().all(Option::is_none));
+    }
+}
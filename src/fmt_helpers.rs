@@ -52,10 +52,10 @@ pub fn fmt_hertz(f: ExtNonNegF64) -> String {
 //    u8 --b.into()--> char --c.to_string()--> String ------------------s.as_str()--> &str
 //    u8 --------------------------------------------str::from_utf8(&[b]).unwrap()--> &str
 pub trait FmtTetromino {
-    fn linestr(&self) -> &str;
-    fn linestr_ascii(&self) -> &str;
-    fn charstr(&self) -> &str;
-    fn charstr_ascii(&self) -> &str;
+    fn linestr(&self) -> &'static str;
+    fn linestr_ascii(&self) -> &'static str;
+    fn charstr(&self) -> &'static str;
+    fn charstr_ascii(&self) -> &'static str;
 }
 
 impl FmtTetromino for Tetromino {
@@ -112,6 +112,13 @@ impl FmtTetromino for Tetromino {
     }
 }
 
+/// The tetromino named by the given letter (see [`FmtTetromino::charstr_ascii`]), e.g. `'T'`.
+pub fn tetromino_from_char(c: char) -> Option<Tetromino> {
+    Tetromino::VARIANTS
+        .into_iter()
+        .find(|tet| tet.charstr_ascii() == c.encode_utf8(&mut [0; 4]))
+}
+
 pub fn fmt_tetromino_counts(counts: &[u32; Tetromino::VARIANTS.len()]) -> String {
     counts
         .iter()
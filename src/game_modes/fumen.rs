@@ -0,0 +1,409 @@
+//! Encoding and decoding of fumen strings (<https://fumen.zui.jp/>), version 115.
+
+use std::num::NonZeroU8;
+
+use falling_tetromino_engine::{Board, Piece, Tetromino};
+
+const ENCODE_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const COMMENT_TABLE: &[u8; 95] = b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+const FIELD_WIDTH: usize = 10;
+/// Number of visible rows of a fumen field, below which lies one extra garbage row.
+const FIELD_TOP: usize = 23;
+const FIELD_BLOCKS: usize = (FIELD_TOP + 1) * FIELD_WIDTH;
+
+/// Fumen's color of garbage blocks; colors `1..=7` are the tetrominos in order `I L O Z T J S`.
+const GRAY: u8 = 8;
+
+/// Rows of colors, where row 0 is the garbage row and row `y + 1` is the field row `y` (from the bottom).
+type Field = [[u8; FIELD_WIDTH]; FIELD_TOP + 1];
+
+/// A single page of a fumen diagram.
+#[derive(Clone, Debug)]
+pub struct FumenPage {
+    /// The board shown on the page, before its piece (if any) locks.
+    pub board: Board,
+    /// Whether the board was edited on this page, and not just the result of the previous page.
+    pub field_changed: bool,
+    /// The piece placed on the page.
+    pub tetromino: Option<Tetromino>,
+    /// The page's comment, which is carried over from the previous page if not set.
+    pub comment: String,
+}
+
+fn fumen_color(tetromino: Tetromino) -> u8 {
+    match tetromino {
+        Tetromino::I => 1,
+        Tetromino::L => 2,
+        Tetromino::O => 3,
+        Tetromino::Z => 4,
+        Tetromino::T => 5,
+        Tetromino::J => 6,
+        Tetromino::S => 7,
+    }
+}
+
+fn fumen_tetromino(color: u8) -> Option<Tetromino> {
+    Tetromino::VARIANTS
+        .into_iter()
+        .find(|tet| fumen_color(*tet) == color)
+}
+
+/// Field positions `(x, y)` of a piece given by its fumen color, rotation and coordinate.
+///
+/// Rotations are numbered `0` = reverse, `1` = right, `2` = spawn, `3` = left.
+fn piece_blocks(color: u8, rotation: usize, coordinate: usize) -> Option<[(isize, isize); 4]> {
+    let tetromino = fumen_tetromino(color)?;
+    let mut x = (coordinate % FIELD_WIDTH) as isize;
+    let mut y = FIELD_TOP as isize - (coordinate / FIELD_WIDTH) as isize - 1;
+    // Fumen stores some pieces at a slightly different position than the one it rotates around.
+    match (tetromino, rotation) {
+        (Tetromino::O, 3) => (x, y) = (x + 1, y - 1),
+        (Tetromino::O, 0) | (Tetromino::I, 0) | (Tetromino::Z, 3) => x += 1,
+        (Tetromino::S, 1) => x -= 1,
+        (Tetromino::O, 2) | (Tetromino::I, 3) | (Tetromino::S, 2) | (Tetromino::Z, 2) => y -= 1,
+        _ => {}
+    }
+    let offsets = match tetromino {
+        Tetromino::I => [(0, 0), (-1, 0), (1, 0), (2, 0)],
+        Tetromino::T => [(0, 0), (-1, 0), (1, 0), (0, 1)],
+        Tetromino::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+        Tetromino::L => [(0, 0), (-1, 0), (1, 0), (1, 1)],
+        Tetromino::J => [(0, 0), (-1, 0), (1, 0), (-1, 1)],
+        Tetromino::S => [(0, 0), (-1, 0), (0, 1), (1, 1)],
+        Tetromino::Z => [(0, 0), (1, 0), (0, 1), (-1, 1)],
+    };
+    Some(offsets.map(|(dx, dy)| match rotation {
+        0 => (x - dx, y - dy),
+        1 => (x + dy, y - dx),
+        2 => (x + dx, y + dy),
+        _ => (x - dy, y + dx),
+    }))
+}
+
+fn board_from_field(field: &Field) -> Board {
+    let grey_tile = NonZeroU8::try_from(254).unwrap();
+
+    let mut board = Board::default();
+    for (line, row) in board.iter_mut().zip(&field[1..]) {
+        for (tile, color) in line.iter_mut().zip(row) {
+            *tile = match *color {
+                0 => None,
+                GRAY => Some(grey_tile),
+                color => fumen_tetromino(color).map(Tetromino::tile_id),
+            };
+        }
+    }
+    board
+}
+
+fn field_from_board(board: &Board) -> Field {
+    let mut field = Field::default();
+    for (row, line) in field[1..].iter_mut().zip(board) {
+        for (color, tile) in row.iter_mut().zip(line) {
+            *color = match tile {
+                None => 0,
+                Some(tile_id) => Tetromino::VARIANTS
+                    .into_iter()
+                    .find(|tet| tet.tile_id() == *tile_id)
+                    .map_or(GRAY, fumen_color),
+            };
+        }
+    }
+    field
+}
+
+/// Reverse JavaScript's `escape`, which fumen applies to comments.
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = s;
+    while let Some(idx) = rest.find('%') {
+        unescaped.push_str(&rest[..idx]);
+        rest = &rest[idx + 1..];
+        let (hex_len, skip) = if rest.starts_with('u') {
+            (4, 1)
+        } else {
+            (2, 0)
+        };
+        let code = rest
+            .get(skip..skip + hex_len)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32);
+        match code {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[skip + hex_len..];
+            }
+            None => unescaped.push('%'),
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Base64 digits of a fumen, read as little-endian numbers.
+struct Values {
+    digits: Vec<usize>,
+    idx: usize,
+}
+
+impl Values {
+    fn is_empty(&self) -> bool {
+        self.idx >= self.digits.len()
+    }
+
+    fn poll(&mut self, len: usize) -> Result<usize, String> {
+        let digits = self
+            .digits
+            .get(self.idx..self.idx + len)
+            .ok_or("fumen ends unexpectedly")?;
+        self.idx += len;
+        Ok(digits
+            .iter()
+            .rev()
+            .fold(0, |value, digit| value * 64 + digit))
+    }
+}
+
+fn push_value(s: &mut String, mut value: usize, len: usize) {
+    for _ in 0..len {
+        s.push(char::from(ENCODE_TABLE[value % 64]));
+        value /= 64;
+    }
+}
+
+/// Decode all pages of a fumen, which may also be given as full URL.
+pub fn decode(fumen: &str) -> Result<Vec<FumenPage>, String> {
+    let Some((_, data)) = fumen.trim().split_once("115@") else {
+        return Err("not a fumen (v115) string".to_owned());
+    };
+    let digits = data
+        .chars()
+        .filter(|c| *c != '?')
+        .map(|c| {
+            ENCODE_TABLE
+                .iter()
+                .position(|e| char::from(*e) == c)
+                .ok_or(format!("invalid fumen character {c:?}"))
+        })
+        .collect::<Result<_, _>>()?;
+    let mut values = Values { digits, idx: 0 };
+
+    let mut pages = Vec::new();
+    let mut field = Field::default();
+    let mut comment = String::new();
+    let mut repeat_count = 0;
+
+    while !values.is_empty() {
+        // Field, given as changes to the previous field.
+        let field_changed = if repeat_count > 0 {
+            repeat_count -= 1;
+            false
+        } else {
+            let mut idx = 0;
+            let mut field_changed = true;
+            while idx < FIELD_BLOCKS {
+                let value = values.poll(2)?;
+                let (diff, count) = (value / FIELD_BLOCKS, value % FIELD_BLOCKS + 1);
+                if diff == 8 && count == FIELD_BLOCKS {
+                    field_changed = false;
+                }
+                for _ in 0..count {
+                    let Some(row) = FIELD_TOP.checked_sub(idx / FIELD_WIDTH) else {
+                        return Err("fumen field too large".to_owned());
+                    };
+                    let color = &mut field[row][idx % FIELD_WIDTH];
+                    *color = (*color as usize + diff)
+                        .checked_sub(8)
+                        .filter(|color| *color <= GRAY as usize)
+                        .ok_or("invalid fumen field")? as u8;
+                    idx += 1;
+                }
+            }
+            if !field_changed {
+                repeat_count = values.poll(1)?;
+            }
+            field_changed
+        };
+
+        // Action, i.e. the page's piece and flags.
+        let mut action = values.poll(3)?;
+        let mut take = |n| {
+            let value = action % n;
+            action /= n;
+            value
+        };
+        let (color, rotation, coordinate) = (take(8) as u8, take(4), take(FIELD_BLOCKS));
+        let [rise, mirror, _colorize, has_comment] = [(); 4].map(|()| take(2) == 1);
+        let lock = take(2) == 0;
+
+        if has_comment {
+            let len = values.poll(2)?;
+            let mut chars = Vec::new();
+            for _ in 0..len.div_ceil(4) {
+                let mut value = values.poll(5)?;
+                for _ in 0..4 {
+                    chars.push(char::from(*COMMENT_TABLE.get(value % 96).unwrap_or(&b' ')));
+                    value /= 96;
+                }
+            }
+            comment = unescape(&chars.into_iter().take(len).collect::<String>());
+        }
+
+        pages.push(FumenPage {
+            board: board_from_field(&field),
+            field_changed,
+            tetromino: fumen_tetromino(color),
+            comment: comment.clone(),
+        });
+
+        // Advance the field to what the next page is based on.
+        if lock {
+            if let Some(blocks) = piece_blocks(color, rotation, coordinate) {
+                for (x, y) in blocks {
+                    if (0..FIELD_WIDTH as isize).contains(&x)
+                        && (0..FIELD_TOP as isize).contains(&y)
+                    {
+                        field[y as usize + 1][x as usize] = color;
+                    }
+                }
+            }
+            let rows = field[1..]
+                .iter()
+                .filter(|row| row.contains(&0))
+                .copied()
+                .collect::<Vec<_>>();
+            for (row, kept) in field[1..]
+                .iter_mut()
+                .zip(rows.into_iter().chain(std::iter::repeat([0; FIELD_WIDTH])))
+            {
+                *row = kept;
+            }
+            if rise {
+                field.copy_within(0..FIELD_TOP, 1);
+                field[0] = [0; FIELD_WIDTH];
+            }
+            if mirror {
+                for row in &mut field[1..] {
+                    row.reverse();
+                }
+            }
+        }
+    }
+
+    if pages.is_empty() {
+        return Err("fumen has no pages".to_owned());
+    }
+
+    Ok(pages)
+}
+
+/// Encode a board as single-page fumen, optionally showing a piece on it.
+///
+/// Only the lowest 23 rows of the board fit into a fumen.
+pub fn encode(board: &Board, piece: Option<&Piece>) -> String {
+    let mut fumen = "v115@".to_owned();
+
+    // Field, as changes to the empty field.
+    let field = field_from_board(board);
+    let diffs = (0..FIELD_BLOCKS)
+        .map(|idx| field[FIELD_TOP - idx / FIELD_WIDTH][idx % FIELD_WIDTH] as usize + 8)
+        .collect::<Vec<_>>();
+    for run in diffs.chunk_by(|a, b| a == b) {
+        push_value(&mut fumen, run[0] * FIELD_BLOCKS + run.len() - 1, 2);
+    }
+    if diffs.iter().all(|diff| *diff == 8) {
+        // The field is unchanged for one page.
+        push_value(&mut fumen, 0, 1);
+    }
+
+    // Action, with the piece at the rotation and coordinate that covers the same blocks.
+    let (color, rotation, coordinate) = piece
+        .and_then(|piece| {
+            let color = fumen_color(piece.tetromino);
+            let mut blocks = piece.tiles().map(|(coord, _)| coord);
+            blocks.sort();
+            (0..4).find_map(|rotation| {
+                (0..FIELD_BLOCKS).find_map(|coordinate| {
+                    let mut fumen_blocks = piece_blocks(color, rotation, coordinate)?;
+                    fumen_blocks.sort();
+                    (fumen_blocks == blocks).then_some((color, rotation, coordinate))
+                })
+            })
+        })
+        .unwrap_or((0, 0, 0));
+    // Flags, from the highest bit: no lock, comment, colorize, mirror, rise.
+    let flags = 0b00100;
+    let action = ((flags * FIELD_BLOCKS + coordinate) * 4 + rotation) * 8 + color as usize;
+    push_value(&mut fumen, action, 3);
+
+    fumen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_single_page_board() {
+        // Four lines of garbage with the rightmost column open, as in the `--fumen` example.
+        let pages = decode("v115@9gI8AeI8AeI8AeI8KeAgH").unwrap();
+
+        let [page] = pages.as_slice() else {
+            panic!("expected one page, got {}", pages.len());
+        };
+        let grey_tile = NonZeroU8::try_from(254).ok();
+        for (y, line) in page.board.iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                let expected = if y < 4 && x < 9 { grey_tile } else { None };
+                assert_eq!(*tile, expected, "tile at ({x}, {y})");
+            }
+        }
+        assert_eq!(page.tetromino, None);
+    }
+
+    #[test]
+    fn decodes_placed_piece_onto_next_page() {
+        // A T placed flat in the middle of the empty field, followed by an empty page.
+        let pages = decode("https://fumen.zui.jp/?v115@vhBVQJAgH").unwrap();
+
+        let [first, second] = pages.as_slice() else {
+            panic!("expected two pages, got {}", pages.len());
+        };
+        assert_eq!(first.board, Board::default());
+        assert!(!first.field_changed);
+        assert_eq!(first.tetromino, Some(Tetromino::T));
+
+        let t_tile = Some(Tetromino::T.tile_id());
+        let mut expected_board = Board::default();
+        for (x, y) in [(3, 0), (4, 0), (5, 0), (4, 1)] {
+            expected_board[y][x] = t_tile;
+        }
+        assert_eq!(second.board, expected_board);
+        assert!(!second.field_changed);
+        assert_eq!(second.tetromino, None);
+    }
+
+    #[test]
+    fn encoded_board_decodes_to_same_board() {
+        let mut board = Board::default();
+        board[0] = [NonZeroU8::try_from(254).ok(); 10];
+        board[0][2] = None;
+        board[1][5] = Some(Tetromino::S.tile_id());
+
+        let fumen = encode(&board, None);
+        let pages = decode(&fumen).unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].board, board);
+    }
+
+    #[test]
+    fn rejects_invalid_fumens() {
+        assert!(decode("").is_err());
+        assert!(decode("v110@vhAAgH").is_err());
+        assert!(decode("v115@").is_err());
+        assert!(decode("v115@vhAAg").is_err());
+        assert!(decode("v115@vh!AgH").is_err());
+    }
+}
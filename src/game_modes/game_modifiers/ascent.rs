@@ -16,18 +16,23 @@ pub struct Ascent {
 
 impl Ascent {
     pub const MOD_ID: &str = stringify!(Ascent);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[];
 
-    pub fn build(builder: &GameBuilder) -> Game {
-        let modifier = Box::new(Self { height_loaded: 0 });
-
+    pub fn prepare(builder: &mut GameBuilder) -> Box<dyn GameModifier> {
         builder
-            .clone()
             .lock_delay_params(DelayParameters::constant(ExtDuration::Infinite))
             .game_limits(GameLimits::single(
                 Stat::TimeElapsed(Duration::from_secs(2 * 60)),
                 true,
-            ))
-            .build_modded(vec![modifier])
+            ));
+
+        Box::new(Self { height_loaded: 0 })
+    }
+
+    pub fn build(builder: &GameBuilder) -> Game {
+        let mut builder = builder.clone();
+        let modifier = Self::prepare(&mut builder);
+        builder.build_modded(vec![modifier])
     }
 }
 
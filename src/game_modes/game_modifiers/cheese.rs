@@ -6,6 +6,8 @@ use falling_tetromino_engine::{
 
 use rand::seq::SliceRandom;
 
+use super::StartBoard;
+
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
 )]
@@ -20,27 +22,35 @@ pub struct Cheese {
 
 impl Cheese {
     pub const MOD_ID: &str = stringify!(Cheese);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[StartBoard::MOD_ID];
 
-    pub fn build(
-        builder: &GameBuilder,
+    pub fn prepare(
+        builder: &mut GameBuilder,
         tiles_per_line: NonZeroUsize,
         cheese_limit: Option<NonZeroU32>,
-    ) -> Game {
-        let modifier = Box::new(Self {
+    ) -> Box<dyn GameModifier> {
+        builder.game_limits(match cheese_limit {
+            Some(c) => GameLimits::single(Stat::PointsScored(c.get()), true),
+            None => GameLimits::new(),
+        });
+
+        Box::new(Self {
             tiles_per_line,
             cheese_limit,
             cheese_eaten: 0,
             cheese_last_eaten: 0,
             cheese_generated: 0,
-        });
+        })
+    }
 
-        builder
-            .clone()
-            .game_limits(match cheese_limit {
-                Some(c) => GameLimits::single(Stat::PointsScored(c.get()), true),
-                None => GameLimits::new(),
-            })
-            .build_modded(vec![modifier])
+    pub fn build(
+        builder: &GameBuilder,
+        tiles_per_line: NonZeroUsize,
+        cheese_limit: Option<NonZeroU32>,
+    ) -> Game {
+        let mut builder = builder.clone();
+        let modifier = Self::prepare(&mut builder, tiles_per_line, cheese_limit);
+        builder.build_modded(vec![modifier])
     }
 }
 
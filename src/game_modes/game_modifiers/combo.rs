@@ -4,6 +4,9 @@ use falling_tetromino_engine::{
     Game, GameAccess, GameBuilder, GameEndCause, GameLimits, GameModifier, Line, NotificationFeed,
     Phase, Stat, Tetromino,
 };
+
+use super::StartBoard;
+
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
 )]
@@ -16,25 +19,33 @@ pub struct Combo {
 
 impl Combo {
     pub const MOD_ID: &str = stringify!(Combo);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[StartBoard::MOD_ID];
 
-    pub fn build(
-        builder: &GameBuilder,
+    pub fn prepare(
+        builder: &mut GameBuilder,
         initial_layout: u16,
         combo_limit: Option<NonZeroU32>,
-    ) -> Game {
-        let modifier = Box::new(Self {
+    ) -> Box<dyn GameModifier> {
+        builder.game_limits(match combo_limit {
+            Some(c) => GameLimits::single(Stat::PointsScored(c.get()), true),
+            None => GameLimits::new(),
+        });
+
+        Box::new(Self {
             initial_layout,
             combo_limit,
             height_loaded: 0,
-        });
+        })
+    }
 
-        builder
-            .clone()
-            .game_limits(match combo_limit {
-                Some(c) => GameLimits::single(Stat::PointsScored(c.get()), true),
-                None => GameLimits::new(),
-            })
-            .build_modded(vec![modifier])
+    pub fn build(
+        builder: &GameBuilder,
+        initial_layout: u16,
+        combo_limit: Option<NonZeroU32>,
+    ) -> Game {
+        let mut builder = builder.clone();
+        let modifier = Self::prepare(&mut builder, initial_layout, combo_limit);
+        builder.build_modded(vec![modifier])
     }
 }
 
@@ -1,37 +1,54 @@
 use std::num::{NonZeroU32, NonZeroUsize};
 
-use falling_tetromino_engine::{Game, GameBuilder, GameModifier};
+use falling_tetromino_engine::{Game, GameBuilder, GameModifier, InGameTime, Tetromino};
 
 mod ascent;
 mod cheese;
 mod combo;
+mod piece_sequence;
 mod print_msgs;
 mod print_recency_stats;
 mod puzzle;
+mod sandbox;
+mod script;
 mod start_board;
 
 pub use ascent::Ascent;
 pub use cheese::Cheese;
 pub use combo::Combo;
+pub use piece_sequence::PieceSequence;
 pub use print_msgs::PrintMsgs;
 pub use print_recency_stats::PrintRecencyStats;
 pub use puzzle::Puzzle;
+pub use sandbox::{Sandbox, SandboxEdit};
+pub use script::Script;
 pub use start_board::StartBoard;
 
-pub fn reconstruct_build_modded<'a>(
-    builder: &'a GameBuilder,
+/// Build a game with the given mods.
+///
+/// Mods are added to the game in order, building mods also adjusting the builder beforehand.
+/// Two building mods can only be combined if both declare the other in their `COMPATIBLE_MOD_IDS`.
+/// Mods that only print messages are added after the game was built.
+pub fn reconstruct_build_modded(
+    builder: &GameBuilder,
     mod_ids_args: &Vec<(String, String)>,
 ) -> Result<(Game, Vec<String>), String> {
+    let mut builder = builder.clone();
+
+    let mut mods: Vec<Box<dyn GameModifier>> = Vec::new();
     let mut compounding_mods: Vec<Box<dyn GameModifier>> = Vec::new();
 
-    #[allow(clippy::type_complexity)]
-    let mut building_mod: Option<(&str, Box<dyn FnOnce(&'a GameBuilder) -> Game>)> = None;
+    let mut building_mod_ids: Vec<(&str, &[&str])> = Vec::new();
 
-    let mut store_building_mod = |mod_id, build| {
-        if let Some((other_id, _)) = building_mod {
-            return Err(format!("incompatible mods: {other_id:?} + {mod_id:?}"));
+    let mut check_compatible = |mod_id: &'static str, compatible_mod_ids: &'static [&str]| {
+        for (other_id, other_compatible_mod_ids) in &building_mod_ids {
+            if !(compatible_mod_ids.contains(other_id)
+                && other_compatible_mod_ids.contains(&mod_id))
+            {
+                return Err(format!("incompatible mods: {other_id:?} + {mod_id:?}"));
+            }
         }
-        building_mod.replace((mod_id, build));
+        building_mod_ids.push((mod_id, compatible_mod_ids));
         Ok(())
     };
 
@@ -52,26 +69,36 @@ pub fn reconstruct_build_modded<'a>(
 
     for (mod_id, mod_args_str) in mod_ids_args {
         if mod_id == Puzzle::MOD_ID {
-            let build = Box::new(Puzzle::build);
-            store_building_mod(mod_id, build)?;
+            // Built-in stages are stored without args.
+            let custom_stages = if mod_args_str.is_empty() {
+                Vec::new()
+            } else {
+                get_mod_args::<Option<Vec<(String, Vec<Tetromino>)>>>(mod_args_str, mod_id)?
+                    .unwrap_or_default()
+            };
+            check_compatible(Puzzle::MOD_ID, Puzzle::COMPATIBLE_MOD_IDS)?;
+            mods.push(Puzzle::prepare(&mut builder, custom_stages));
         } else if mod_id == Ascent::MOD_ID {
-            let build = Box::new(Ascent::build);
-            store_building_mod(mod_id, build)?;
+            check_compatible(Ascent::MOD_ID, Ascent::COMPATIBLE_MOD_IDS)?;
+            mods.push(Ascent::prepare(&mut builder));
         } else if mod_id == Cheese::MOD_ID {
             let (tiles_per_line, cheese_limit): (NonZeroUsize, Option<NonZeroU32>) =
                 get_mod_args(mod_args_str, mod_id)?;
-            let build =
-                Box::new(move |builder| Cheese::build(builder, tiles_per_line, cheese_limit));
-            store_building_mod(mod_id, build)?;
+            check_compatible(Cheese::MOD_ID, Cheese::COMPATIBLE_MOD_IDS)?;
+            mods.push(Cheese::prepare(&mut builder, tiles_per_line, cheese_limit));
         } else if mod_id == Combo::MOD_ID {
             let (initial_layout, combo_limit): (u16, Option<NonZeroU32>) =
                 get_mod_args(mod_args_str, mod_id)?;
-            let build = Box::new(move |builder| Combo::build(builder, initial_layout, combo_limit));
-            store_building_mod(mod_id, build)?;
+            check_compatible(Combo::MOD_ID, Combo::COMPATIBLE_MOD_IDS)?;
+            mods.push(Combo::prepare(&mut builder, initial_layout, combo_limit));
+        } else if mod_id == Sandbox::MOD_ID {
+            let edits: Vec<(InGameTime, SandboxEdit)> = get_mod_args(mod_args_str, mod_id)?;
+            check_compatible(Sandbox::MOD_ID, Sandbox::COMPATIBLE_MOD_IDS)?;
+            mods.push(Sandbox::prepare(&mut builder, edits));
         } else if mod_id == StartBoard::MOD_ID {
             let encoded_board: String = get_mod_args(mod_args_str, mod_id)?;
-            let build = Box::new(move |builder| StartBoard::build(builder, encoded_board));
-            store_building_mod(mod_id, build)?;
+            check_compatible(StartBoard::MOD_ID, StartBoard::COMPATIBLE_MOD_IDS)?;
+            mods.push(StartBoard::prepare(&mut builder, encoded_board));
         } else if mod_id == PrintRecencyStats::MOD_ID {
             let modifier = PrintRecencyStats::modifier();
             compounding_mods.push(modifier);
@@ -79,16 +106,18 @@ pub fn reconstruct_build_modded<'a>(
             let messages: Vec<String> = get_mod_args(mod_args_str, mod_id)?;
             let modifier = PrintMsgs::modifier(messages);
             compounding_mods.push(modifier);
+        } else if mod_id == PieceSequence::MOD_ID {
+            let sequence_str: String = get_mod_args(mod_args_str, mod_id)?;
+            mods.push(PieceSequence::modifier(sequence_str)?);
+        } else if mod_id == Script::MOD_ID {
+            let (name, source): (String, String) = get_mod_args(mod_args_str, mod_id)?;
+            mods.push(Script::modifier(name, source)?);
         } else {
             unrecognized_mod_ids.push(mod_id.to_owned());
         }
     }
 
-    let mut game = if let Some((_, build)) = building_mod {
-        build(builder)
-    } else {
-        builder.build()
-    };
+    let mut game = builder.build_modded(mods);
 
     game.modifiers.extend(compounding_mods);
 
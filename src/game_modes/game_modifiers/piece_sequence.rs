@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+
+use falling_tetromino_engine::{GameAccess, GameModifier, InGameTime, NotificationFeed, Tetromino};
+
+use crate::fmt_helpers::tetromino_from_char;
+
+/// Deals the pieces of a fixed sequence instead of the generated ones.
+///
+/// The sequence is given as string of tetromino letters (e.g. `"IJLOSTZ"`), and repeats if it ends in `*`.
+/// Once a non-repeating sequence is used up, pieces are generated as usual again.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub struct PieceSequence {
+    sequence_str: String,
+
+    sequence: Vec<Tetromino>,
+    repeat: bool,
+    /// Index of the first piece in the sequence that has not spawned yet.
+    next_idx: usize,
+}
+
+impl PieceSequence {
+    pub const MOD_ID: &str = stringify!(PieceSequence);
+
+    pub fn modifier(sequence_str: String) -> Result<Box<dyn GameModifier>, String> {
+        let (sequence, repeat) = Self::parse(&sequence_str)?;
+
+        Ok(Box::new(Self {
+            sequence_str,
+            sequence,
+            repeat,
+            next_idx: 0,
+        }))
+    }
+
+    /// Parse a sequence string into its tetrominos and whether it repeats.
+    pub fn parse(sequence_str: &str) -> Result<(Vec<Tetromino>, bool), String> {
+        let sequence_str = sequence_str.trim();
+        let (letters, repeat) = match sequence_str.strip_suffix('*') {
+            Some(letters) => (letters, true),
+            None => (sequence_str, false),
+        };
+
+        let sequence = letters
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                tetromino_from_char(c.to_ascii_uppercase())
+                    .ok_or(format!("invalid piece {c:?} (expected one of IJLOSTZ)"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if sequence.is_empty() {
+            return Err("empty piece sequence".to_owned());
+        }
+
+        Ok((sequence, repeat))
+    }
+
+    fn piece(&self, idx: usize) -> Option<Tetromino> {
+        if self.repeat {
+            Some(self.sequence[idx % self.sequence.len()])
+        } else {
+            self.sequence.get(idx).copied()
+        }
+    }
+
+    /// Overwrite the front of the preview with the upcoming pieces of the sequence.
+    fn overwrite_preview(&self, piece_preview: &mut VecDeque<Tetromino>, len: usize) {
+        for i in 0..len {
+            let Some(tet) = self.piece(self.next_idx + i) else {
+                break;
+            };
+            match piece_preview.get_mut(i) {
+                Some(preview_tet) => *preview_tet = tet,
+                None => piece_preview.push_back(tet),
+            }
+        }
+    }
+}
+
+impl GameModifier for PieceSequence {
+    fn id(&self) -> String {
+        Self::MOD_ID.to_owned()
+    }
+
+    fn args(&self) -> String {
+        serde_json::to_string(&self.sequence_str).unwrap()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn GameModifier>, String> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn on_spawn_pre(
+        &mut self,
+        game: GameAccess,
+        _feed: &mut NotificationFeed,
+        _time: &mut InGameTime,
+    ) {
+        // Holding puts the held piece in front of the full preview, it is not from the sequence.
+        if game.state.piece_preview.len() > game.config.piece_preview_count {
+            return;
+        }
+
+        // Make sure there is at least the piece about to spawn, even without preview.
+        let len = game.config.piece_preview_count.max(1);
+        self.overwrite_preview(&mut game.state.piece_preview, len);
+
+        if self.piece(self.next_idx).is_some() {
+            self.next_idx += 1;
+        }
+    }
+
+    fn on_spawn_post(&mut self, game: GameAccess, _feed: &mut NotificationFeed) {
+        // Replace the newly generated piece at the end of the preview.
+        let len = game.config.piece_preview_count;
+        self.overwrite_preview(&mut game.state.piece_preview, len);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{collections::VecDeque, num::NonZeroU8, time::Duration};
 
 use falling_tetromino_engine::{
-    Button, DelayParameters, Game, GameAccess, GameBuilder, GameEndCause, GameModifier, InGameTime,
-    Input, Line, Notification, NotificationFeed, Phase, State, Tetromino,
+    Board, Button, DelayParameters, Game, GameAccess, GameBuilder, GameEndCause, GameModifier,
+    InGameTime, Input, Line, Notification, NotificationFeed, Phase, State, Tetromino,
 };
 
+use super::StartBoard;
+
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
 )]
@@ -14,27 +16,59 @@ pub struct Puzzle {
     stage_tet_count: usize,
     stage_attempts: usize,
     end_post_spawn: Option<bool>,
+    /// Stages used instead of the built-in ones if nonempty, given by encoded board and the pieces to clear it with.
+    custom_stages: Vec<(String, Vec<Tetromino>)>,
 }
 
 impl Puzzle {
     pub const MOD_ID: &str = stringify!(Puzzle);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[];
 
-    pub fn build(builder: &GameBuilder) -> Game {
-        let modifier = Box::new(Self {
+    pub fn prepare(
+        builder: &mut GameBuilder,
+        custom_stages: Vec<(String, Vec<Tetromino>)>,
+    ) -> Box<dyn GameModifier> {
+        builder
+            .fall_delay_params(DelayParameters::constant(
+                Duration::from_millis(1000).into(),
+            ))
+            .piece_preview_count(0);
+
+        Box::new(Self {
             init: false,
             stage_idx: 0,
             stage_tet_count: 0,
             stage_attempts: 0,
             end_post_spawn: None,
-        });
+            custom_stages,
+        })
+    }
 
-        builder
-            .clone()
-            .fall_delay_params(DelayParameters::constant(
-                Duration::from_millis(1000).into(),
-            ))
-            .piece_preview_count(0)
-            .build_modded(vec![modifier])
+    pub fn build(builder: &GameBuilder) -> Game {
+        let mut builder = builder.clone();
+        let modifier = Self::prepare(&mut builder, Vec::new());
+        builder.build_modded(vec![modifier])
+    }
+
+    /// The index of the stage a notification reports as cleared, if it is one.
+    pub fn stage_cleared(notification: &Notification) -> Option<usize> {
+        let Notification::Custom(text) = notification else {
+            return None;
+        };
+        let stage_nr = text
+            .strip_prefix(Self::STAGE_CLEARED_PREFIX)?
+            .strip_suffix(Self::STAGE_CLEARED_SUFFIX)?;
+
+        stage_nr.parse::<usize>().ok()?.checked_sub(1)
+    }
+
+    fn stage_cleared_notification(stage_idx: usize) -> Notification {
+        Notification::Custom(format!(
+            "{}{}{}",
+            Self::STAGE_CLEARED_PREFIX,
+            stage_idx + 1,
+            Self::STAGE_CLEARED_SUFFIX
+        ))
     }
 }
 
@@ -44,7 +78,12 @@ impl GameModifier for Puzzle {
     }
 
     fn args(&self) -> String {
-        "".to_owned()
+        // Keep args of the built-in stages empty, as they have always been.
+        if self.custom_stages.is_empty() {
+            "".to_owned()
+        } else {
+            serde_json::to_string(&self.custom_stages).unwrap()
+        }
     }
 
     fn try_clone(&self) -> Result<Box<dyn GameModifier>, String> {
@@ -100,12 +139,14 @@ impl GameModifier for Puzzle {
         // May have failed or succeeded, load in correct puzzle for each case.
 
         if stage_is_success {
+            feed.push((Self::stage_cleared_notification(self.stage_idx), *time));
+
             // Move on to new stage.
             self.stage_attempts = 0;
             self.stage_idx += 1;
 
             // Done with all stages, game completed.
-            if self.stage_idx == Self::STAGES_LEN {
+            if self.stage_idx == self.stages_len() {
                 self.end_post_spawn = Some(true);
 
                 return;
@@ -180,22 +221,40 @@ impl GameModifier for Puzzle {
 }
 
 impl Puzzle {
+    fn stages_len(&self) -> usize {
+        if self.custom_stages.is_empty() {
+            Self::STAGES_LEN
+        } else {
+            self.custom_stages.len()
+        }
+    }
+
     fn load_stage(&mut self, state: &mut State) {
+        if let Some((encoded_board, stage_tetrominos)) = self.custom_stages.get(self.stage_idx) {
+            state.board = StartBoard::decode_board(encoded_board);
+            state.piece_preview = stage_tetrominos.iter().copied().collect();
+            self.stage_tet_count = stage_tetrominos.len();
+            return;
+        }
+
         let (_stage_name, stage_lines, stage_tetrominos) = Self::get_stage_data(self.stage_idx);
 
+        state.board = Self::stage_board(&stage_lines);
+
+        // Load in stage tetrominos.
+        state.piece_preview.clone_from(&stage_tetrominos);
+
+        // Save stage length.
+        self.stage_tet_count = stage_tetrominos.len();
+    }
+
+    fn stage_board(stage_lines: &[&[u8; 10]]) -> Board {
+        let mut board = Board::default();
+
         let grey_tile = Some(NonZeroU8::try_from(254).unwrap());
-        for (stage_line, game_line) in stage_lines
-            .iter()
-            .rev()
-            .chain(std::iter::repeat(&&[b' '; 10]))
-            .zip(state.board.iter_mut())
-        {
-            *game_line = Line::default();
+        for (stage_line, game_line) in stage_lines.iter().rev().zip(board.iter_mut()) {
             if stage_line.iter().any(|c| c != &b' ') {
-                for (game_cell, puzzle_tile) in game_line
-                    .iter_mut()
-                    .zip(stage_line.iter().chain(std::iter::repeat(&b'O')))
-                {
+                for (game_cell, puzzle_tile) in game_line.iter_mut().zip(stage_line.iter()) {
                     if puzzle_tile != &b' ' {
                         *game_cell = grey_tile;
                     }
@@ -203,16 +262,44 @@ impl Puzzle {
             }
         }
 
-        // Load in stage tetrominos.
-        state.piece_preview.clone_from(&stage_tetrominos);
+        board
+    }
 
-        // Save stage length.
-        self.stage_tet_count = stage_tetrominos.len();
+    /// All stages, by name, board and the pieces to clear it with; The built-in ones unless custom stages are given.
+    pub fn stages(
+        custom_stages: &[(String, Vec<Tetromino>)],
+    ) -> Vec<(String, Board, Vec<Tetromino>)> {
+        if custom_stages.is_empty() {
+            (0..Self::STAGES_LEN)
+                .map(|idx| {
+                    let (stage_name, stage_lines, stage_tetrominos) = Self::get_stage_data(idx);
+                    (
+                        stage_name.to_owned(),
+                        Self::stage_board(&stage_lines),
+                        stage_tetrominos.into(),
+                    )
+                })
+                .collect()
+        } else {
+            custom_stages
+                .iter()
+                .enumerate()
+                .map(|(idx, (encoded_board, stage_tetrominos))| {
+                    (
+                        format!("Stage {}", idx + 1),
+                        StartBoard::decode_board(encoded_board),
+                        stage_tetrominos.clone(),
+                    )
+                })
+                .collect()
+        }
     }
 }
 
 impl Puzzle {
     const MAX_STAGE_ATTEMPTS: usize = 4;
+    const STAGE_CLEARED_PREFIX: &str = "Stage ";
+    const STAGE_CLEARED_SUFFIX: &str = " cleared!";
     const STAGES_LEN: usize = 24;
 
     #[allow(clippy::type_complexity)]
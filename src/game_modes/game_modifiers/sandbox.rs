@@ -0,0 +1,237 @@
+use std::{collections::VecDeque, num::NonZeroU8};
+
+use falling_tetromino_engine::{
+    Board, DelayParameters, ExtDuration, Game, GameAccess, GameBuilder, GameModifier, InGameTime,
+    Input, Notification, NotificationFeed, Phase, Tetromino, UpdateGameError,
+};
+
+/// An edit of the game done by hand while studying a position.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub enum SandboxEdit {
+    /// Choose the piece to spawn next.
+    SetNext(Tetromino),
+    /// Fill an empty cell of the board or empty a filled one.
+    ToggleCell(usize, usize),
+    /// Go back to before the last piece was locked.
+    StepBack,
+}
+
+/// Everything needed to go back to before a piece was locked.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
+)]
+struct Snapshot {
+    board: Board,
+    piece_held: Option<(Tetromino, bool)>,
+    piece_preview: VecDeque<Tetromino>,
+    tetromino: Tetromino,
+    pieces_locked: [u32; Tetromino::VARIANTS.len()],
+    lineclears: u32,
+    consecutive_line_clears: u32,
+    points: u32,
+}
+
+/// Free play for studying positions: No gravity, infinite hold, and the board and next piece can be edited by hand.
+///
+/// Edits are part of the modifier's arguments, stored alongside the in-game time they were made at (see [`Sandbox::add_edit`]).
+/// They are carried out once the game reaches that time, so that replays reproduce them even if other inputs were inserted or deleted (e.g. in the TAS editor).
+/// For this to not depend on how often the game is updated, it has to be updated with [`Sandbox::update_game`].
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
+)]
+pub struct Sandbox {
+    edits: Vec<(InGameTime, SandboxEdit)>,
+
+    edits_carried_out: usize,
+    snapshots: Vec<Snapshot>,
+}
+
+impl Sandbox {
+    pub const MOD_ID: &str = stringify!(Sandbox);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[];
+
+    pub fn prepare(
+        builder: &mut GameBuilder,
+        edits: Vec<(InGameTime, SandboxEdit)>,
+    ) -> Box<dyn GameModifier> {
+        builder
+            .fall_delay_params(DelayParameters::constant(ExtDuration::Infinite))
+            .lock_delay_params(DelayParameters::constant(ExtDuration::Infinite));
+
+        Box::new(Self {
+            edits,
+            edits_carried_out: 0,
+            snapshots: Vec::new(),
+        })
+    }
+
+    pub fn build(builder: &GameBuilder) -> Game {
+        let mut builder = builder.clone();
+        let modifier = Self::prepare(&mut builder, Vec::new());
+        builder.build_modded(vec![modifier])
+    }
+
+    /// Add an edit to be carried out at the given time to the arguments of a sandbox.
+    ///
+    /// A game built with the new arguments carries out the edit once it reaches that time, after the edits already made at it.
+    pub fn add_edit(mod_args: &mut String, time: InGameTime, edit: SandboxEdit) {
+        let mut edits: Vec<(InGameTime, SandboxEdit)> =
+            serde_json::from_str(mod_args).unwrap_or_default();
+
+        let idx = edits.partition_point(|(edit_time, _)| *edit_time <= time);
+        edits.insert(idx, (time, edit));
+
+        *mod_args = serde_json::to_string(&edits).unwrap();
+    }
+
+    /// Update the game like [`Game::update`], but first to every time in between at which its sandbox (if any) carries out an edit.
+    ///
+    /// This way edits are carried out at exactly the state of the game at their time, however often the game is updated otherwise.
+    pub fn update_game(
+        game: &mut Game,
+        target_time: InGameTime,
+        player_input: Option<Input>,
+    ) -> Result<NotificationFeed, UpdateGameError> {
+        let edit_times = game
+            .modifiers
+            .iter()
+            .find(|modifier| modifier.id() == Self::MOD_ID)
+            .and_then(|modifier| {
+                serde_json::from_str::<Vec<(InGameTime, SandboxEdit)>>(&modifier.args()).ok()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(edit_time, _)| edit_time)
+            .filter(|edit_time| game.state().time < *edit_time && *edit_time < target_time)
+            .collect::<Vec<_>>();
+
+        let mut feed = Vec::new();
+        for edit_time in edit_times {
+            feed.extend(game.update(edit_time, None)?);
+
+            // The input is of no concern anymore if the game ended in between.
+            if game.has_ended() {
+                return Ok(feed);
+            }
+        }
+        feed.extend(game.update(target_time, player_input)?);
+
+        Ok(feed)
+    }
+
+    fn apply_edit(
+        &mut self,
+        game: &mut GameAccess,
+        feed: &mut NotificationFeed,
+        edit: SandboxEdit,
+    ) {
+        let time = game.state.time;
+        match edit {
+            SandboxEdit::SetNext(tetromino) => match game.state.piece_preview.front_mut() {
+                Some(next_tetromino) => *next_tetromino = tetromino,
+                None => game.state.piece_preview.push_front(tetromino),
+            },
+
+            SandboxEdit::ToggleCell(x, y) => {
+                let is_piece_tile = game.phase.piece().is_some_and(|piece| {
+                    piece
+                        .tiles()
+                        .iter()
+                        .any(|((x_tile, y_tile), _)| (*x_tile, *y_tile) == (x as isize, y as isize))
+                });
+                if is_piece_tile {
+                    return;
+                }
+                if let Some(cell) = game.state.board.get_mut(y).and_then(|line| line.get_mut(x)) {
+                    *cell = match cell {
+                        Some(_) => None,
+                        None => Some(NonZeroU8::try_from(254).unwrap()),
+                    };
+                }
+            }
+
+            SandboxEdit::StepBack => {
+                let Some(snapshot) = self.snapshots.pop() else {
+                    feed.push((
+                        Notification::Custom("(Nothing to step back)".to_owned()),
+                        time,
+                    ));
+                    return;
+                };
+
+                game.state.board = snapshot.board;
+                game.state.piece_held = snapshot.piece_held;
+                game.state.piece_preview = snapshot.piece_preview;
+                // Have the piece that was locked spawn again.
+                game.state.piece_preview.push_front(snapshot.tetromino);
+                game.state.pieces_locked = snapshot.pieces_locked;
+                game.state.lineclears = snapshot.lineclears;
+                game.state.consecutive_line_clears = snapshot.consecutive_line_clears;
+                game.state.points = snapshot.points;
+                *game.phase = Phase::Spawning { spawn_time: time };
+
+                feed.push((Notification::Custom("(Stepped back)".to_owned()), time));
+            }
+        }
+    }
+}
+
+impl GameModifier for Sandbox {
+    fn id(&self) -> String {
+        Self::MOD_ID.to_owned()
+    }
+
+    fn args(&self) -> String {
+        serde_json::to_string(&self.edits).unwrap()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn GameModifier>, String> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn on_time_state_progression_post(
+        &mut self,
+        mut game: GameAccess,
+        feed: &mut NotificationFeed,
+    ) {
+        // Carry out all edits due by now.
+        while let Some((edit_time, edit)) = self.edits.get(self.edits_carried_out).copied() {
+            if game.state.time < edit_time {
+                break;
+            }
+            self.edits_carried_out += 1;
+            self.apply_edit(&mut game, feed, edit);
+        }
+    }
+
+    fn on_spawn_post(&mut self, game: GameAccess, _feed: &mut NotificationFeed) {
+        // Infinite hold.
+        if let Some((_held_tet, swap_allowed)) = &mut game.state.piece_held {
+            *swap_allowed = true;
+        }
+    }
+
+    fn on_lock_pre(
+        &mut self,
+        game: GameAccess,
+        _feed: &mut NotificationFeed,
+        _time: &mut InGameTime,
+    ) {
+        let Some(piece) = game.phase.piece() else {
+            return;
+        };
+
+        self.snapshots.push(Snapshot {
+            board: game.state.board,
+            piece_held: game.state.piece_held,
+            piece_preview: game.state.piece_preview.clone(),
+            tetromino: piece.tetromino,
+            pieces_locked: game.state.pieces_locked,
+            lineclears: game.state.lineclears,
+            consecutive_line_clears: game.state.consecutive_line_clears,
+            points: game.state.points,
+        });
+    }
+}
@@ -0,0 +1,285 @@
+use std::{collections::BTreeSet, num::NonZeroU8};
+
+use falling_tetromino_engine::{
+    GameAccess, GameModifier, InGameTime, Input, Notification, NotificationFeed,
+};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, FuncArgs, Map, Scope, AST};
+
+use crate::fmt_helpers::{tetromino_from_char, FmtTetromino};
+
+/// Runs the hook functions of a user-provided Rhai script.
+///
+/// A hook function defined in the script (e.g. `fn on_spawn_pre() { .. }`) is called with the game as `this`,
+/// which is an object map with the following fields:
+/// * `board`: Array of rows (from the bottom) of tile ids, where 0 is empty.
+/// * `preview`: Array of upcoming tetrominos, e.g. `"T"`.
+/// * `hold`: The held tetromino or `""`, and `hold_swappable`.
+/// * `feed`: Notifications of the current update as text; text pushed to it is shown as message.
+/// * `state`: Object map which is kept between calls.
+/// * `time` (in seconds), `lines`, `points`, `pieces`: Read-only stats.
+///
+/// `on_player_input_received(button, pressed)` can also return `false` to drop the input.
+#[derive(Debug)]
+pub struct Script {
+    name: String,
+    source: String,
+
+    engine: Engine,
+    ast: AST,
+    hooks: BTreeSet<String>,
+    state: Dynamic,
+    failed: bool,
+}
+
+impl Script {
+    pub const MOD_ID: &str = stringify!(Script);
+
+    /// Limit on operations per hook call, so a faulty script cannot hang the game.
+    const MAX_OPERATIONS: u64 = 1_000_000;
+
+    pub fn modifier(name: String, source: String) -> Result<Box<dyn GameModifier>, String> {
+        let engine = Self::engine();
+        let ast = engine
+            .compile(&source)
+            .map_err(|e| format!("script {name:?}: {e}"))?;
+        let hooks = ast.iter_functions().map(|f| f.name.to_owned()).collect();
+
+        Ok(Box::new(Self {
+            name,
+            source,
+            engine,
+            ast,
+            hooks,
+            state: Dynamic::from_map(Map::new()),
+            failed: false,
+        }))
+    }
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(Self::MAX_OPERATIONS);
+        // Printing would mess up the terminal.
+        engine.on_print(|_| {}).on_debug(|_, _, _| {});
+        engine
+    }
+
+    /// Call the hook function if the script defines it, then apply the changes it made to the game.
+    ///
+    /// Once the script fails, an error message is shown and it is not called anymore.
+    fn call_hook(
+        &mut self,
+        hook: &str,
+        game: GameAccess,
+        feed: &mut NotificationFeed,
+        time: InGameTime,
+        args: impl FuncArgs,
+    ) -> Option<Dynamic> {
+        if self.failed || !self.hooks.contains(hook) {
+            return None;
+        }
+
+        let mut this = Dynamic::from_map(self.game_map(&game, feed));
+
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            CallFnOptions::new()
+                .eval_ast(false)
+                .bind_this_ptr(&mut this),
+            &mut Scope::new(),
+            &self.ast,
+            hook,
+            args,
+        );
+
+        match result {
+            Ok(value) => {
+                if let Some(map) = this.try_cast::<Map>() {
+                    self.apply_game_map(map, game, feed, time);
+                }
+                Some(value)
+            }
+            Err(e) => {
+                self.failed = true;
+                feed.push((
+                    Notification::Custom(format!("ERROR: script {:?}: {e}", self.name)),
+                    time,
+                ));
+                None
+            }
+        }
+    }
+
+    fn game_map(&self, game: &GameAccess, feed: &NotificationFeed) -> Map {
+        let board = game
+            .state
+            .board
+            .iter()
+            .map(|line| {
+                let tiles = line
+                    .iter()
+                    .map(|tile| Dynamic::from_int(tile.map_or(0, |id| id.get().into())))
+                    .collect();
+                Dynamic::from_array(tiles)
+            })
+            .collect();
+        let preview = game
+            .state
+            .piece_preview
+            .iter()
+            .map(|tet| Dynamic::from(tet.charstr_ascii()))
+            .collect();
+        let (hold, hold_swappable) = match game.state.piece_held {
+            Some((tet, swappable)) => (tet.charstr_ascii().to_owned(), swappable),
+            None => (String::new(), true),
+        };
+        let feed = feed
+            .iter()
+            .map(|(notification, _)| {
+                Dynamic::from(match notification {
+                    Notification::Custom(message) => message.clone(),
+                    other => format!("{other:?}"),
+                })
+            })
+            .collect();
+
+        let mut map = Map::new();
+        map.insert("board".into(), Dynamic::from_array(board));
+        map.insert("preview".into(), Dynamic::from_array(preview));
+        map.insert("hold".into(), Dynamic::from(hold));
+        map.insert("hold_swappable".into(), Dynamic::from_bool(hold_swappable));
+        map.insert("feed".into(), Dynamic::from_array(feed));
+        map.insert("state".into(), self.state.clone());
+        map.insert(
+            "time".into(),
+            Dynamic::from_float(game.state.time.as_secs_f64()),
+        );
+        map.insert(
+            "lines".into(),
+            Dynamic::from_int(game.state.lineclears.into()),
+        );
+        map.insert("points".into(), Dynamic::from_int(game.state.points.into()));
+        map.insert(
+            "pieces".into(),
+            Dynamic::from_int(game.state.pieces_locked.iter().sum::<u32>().into()),
+        );
+        map
+    }
+
+    fn apply_game_map(
+        &mut self,
+        mut map: Map,
+        game: GameAccess,
+        feed: &mut NotificationFeed,
+        time: InGameTime,
+    ) {
+        let parse_tetromino = |dynamic: Dynamic| {
+            let name = dynamic.into_string().ok()?;
+            tetromino_from_char(name.parse().ok()?)
+        };
+
+        if let Some(board) = map.remove("board").and_then(Dynamic::try_cast::<Array>) {
+            for (line, row) in game.state.board.iter_mut().zip(board) {
+                let Some(row) = row.try_cast::<Array>() else {
+                    continue;
+                };
+                for (tile, id) in line.iter_mut().zip(row) {
+                    if let Ok(id) = id.as_int() {
+                        *tile = u8::try_from(id).ok().and_then(NonZeroU8::new);
+                    }
+                }
+            }
+        }
+
+        if let Some(preview) = map.remove("preview").and_then(Dynamic::try_cast::<Array>) {
+            game.state.piece_preview = preview.into_iter().filter_map(parse_tetromino).collect();
+        }
+
+        if let Some(hold) = map.remove("hold") {
+            let hold_swappable = map
+                .remove("hold_swappable")
+                .and_then(|swappable| swappable.as_bool().ok())
+                .unwrap_or(true);
+            game.state.piece_held = parse_tetromino(hold).map(|tet| (tet, hold_swappable));
+        }
+
+        if let Some(script_feed) = map.remove("feed").and_then(Dynamic::try_cast::<Array>) {
+            // Only entries pushed by the script are new.
+            let new_messages = script_feed.into_iter().skip(feed.len()).collect::<Vec<_>>();
+            for message in new_messages {
+                feed.push((Notification::Custom(message.to_string()), time));
+            }
+        }
+
+        if let Some(state) = map.remove("state") {
+            self.state = state;
+        }
+    }
+}
+
+impl GameModifier for Script {
+    fn id(&self) -> String {
+        Self::MOD_ID.to_owned()
+    }
+
+    fn args(&self) -> String {
+        serde_json::to_string(&(&self.name, &self.source)).unwrap()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn GameModifier>, String> {
+        Ok(Box::new(Self {
+            name: self.name.clone(),
+            source: self.source.clone(),
+            engine: Self::engine(),
+            ast: self.ast.clone(),
+            hooks: self.hooks.clone(),
+            state: self.state.clone(),
+            failed: self.failed,
+        }))
+    }
+
+    fn on_game_built(&mut self, game: GameAccess) {
+        // No notifications can be emitted at this point.
+        let time = game.state.time;
+        self.call_hook("on_game_built", game, &mut Vec::new(), time, ());
+    }
+
+    fn on_player_input_received(
+        &mut self,
+        game: GameAccess,
+        feed: &mut NotificationFeed,
+        time: &mut InGameTime,
+        player_input: &mut Option<Input>,
+    ) {
+        let Some(input) = player_input else {
+            return;
+        };
+        let (button, pressed) = match input {
+            Input::Activate(button) => (button, true),
+            Input::Deactivate(button) => (button, false),
+        };
+        let args = (format!("{button:?}"), pressed);
+
+        let result = self.call_hook("on_player_input_received", game, feed, *time, args);
+        if result.is_some_and(|keep_input| keep_input.as_bool() == Ok(false)) {
+            player_input.take();
+        }
+    }
+
+    fn on_spawn_pre(
+        &mut self,
+        game: GameAccess,
+        feed: &mut NotificationFeed,
+        time: &mut InGameTime,
+    ) {
+        self.call_hook("on_spawn_pre", game, feed, *time, ());
+    }
+
+    fn on_spawn_post(&mut self, game: GameAccess, feed: &mut NotificationFeed) {
+        let time = game.state.time;
+        self.call_hook("on_spawn_post", game, feed, time, ());
+    }
+
+    fn on_lines_clear_post(&mut self, game: GameAccess, feed: &mut NotificationFeed) {
+        let time = game.state.time;
+        self.call_hook("on_lines_clear_post", game, feed, time, ());
+    }
+}
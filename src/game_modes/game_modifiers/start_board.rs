@@ -1,4 +1,8 @@
-use falling_tetromino_engine::{Game, GameAccess, GameBuilder, GameModifier};
+use std::num::NonZeroU8;
+
+use falling_tetromino_engine::{Board, GameAccess, GameBuilder, GameModifier};
+
+use super::{Cheese, Combo};
 
 #[derive(
     PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, serde::Serialize, serde::Deserialize,
@@ -9,11 +13,53 @@ pub struct StartBoard {
 
 impl StartBoard {
     pub const MOD_ID: &str = stringify!(StartBoard);
+    pub const COMPATIBLE_MOD_IDS: &[&str] = &[Cheese::MOD_ID, Combo::MOD_ID];
+
+    pub fn prepare(_builder: &mut GameBuilder, encoded_board: String) -> Box<dyn GameModifier> {
+        Box::new(Self { encoded_board })
+    }
+
+    /// Encode a board as the lines of its tiles from the bottom, with `'X'` = filled and `' '` = empty.
+    pub fn encode_board(board: &Board) -> String {
+        board
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|tile| if tile.is_some() { 'X' } else { ' ' })
+                    .collect::<String>()
+            })
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+
+    pub fn decode_board(board_str: &str) -> Board {
+        let grey_tile = Some(NonZeroU8::try_from(254).unwrap());
+
+        let mut new_board = Board::default();
+
+        let mut chars = board_str.chars();
 
-    pub fn build(builder: &GameBuilder, encoded_board: String) -> Game {
-        let modifier = Box::new(Self { encoded_board });
+        for line in &mut new_board {
+            for tile in line {
+                for char in chars.by_ref() {
+                    if char == ' ' {
+                        // Space = empty tile.
+                        *tile = None;
+                        break;
+                    } else if char == '\n' {
+                        // Newline = ignore, stay at tile but move on to next char.
+                        continue;
+                    } else {
+                        // Otherwise = filled tile.
+                        *tile = grey_tile;
+                        break;
+                    }
+                }
+            }
+        }
 
-        builder.clone().build_modded(vec![modifier])
+        new_board
     }
 }
 
@@ -31,8 +77,7 @@ impl GameModifier for StartBoard {
     }
 
     fn on_game_built(&mut self, game: GameAccess) {
-        let start_board =
-            crate::application::NewGameSettings::decode_board(self.encoded_board.as_str());
+        let start_board = Self::decode_board(self.encoded_board.as_str());
 
         game.state.board = start_board;
     }
@@ -3,9 +3,15 @@ use std::{
     time::Duration,
 };
 
+use chrono::Datelike;
+
 use falling_tetromino_engine::{DelayParameters, ExtDuration, Game, GameBuilder, GameLimits, Stat};
 
+pub mod fumen;
 pub mod game_modifiers;
+mod mode_files;
+
+pub use mode_files::load_mode_files;
 
 // Name, (Stat-to-sort-by, is-order-desc), game-builder-struct-finalizer).
 pub struct GameMode {
@@ -93,6 +99,16 @@ impl GameMode {
         }
     }
 
+    pub const TITLE_SANDBOX: &str = "Sandbox";
+    pub fn sandbox() -> Self {
+        Self {
+            title: Self::TITLE_SANDBOX.to_owned(),
+            description: "Study positions: no gravity, edit board.".to_owned(),
+            stat_and_order_desc: (Stat::PiecesLocked(0), false),
+            build: Box::new(game_modifiers::Sandbox::build),
+        }
+    }
+
     pub const TITLE_CHEESE: &str = "Cheese";
     pub fn cheese(
         cheese_tiles_per_line: NonZeroUsize,
@@ -156,6 +172,35 @@ impl GameMode {
         }
     }
 
+    pub const TITLE_DAILY: &str = "Daily";
+    /// The daily challenge, which derives its seed and rotates through some modes based on the date.
+    pub fn daily(date: chrono::NaiveDate, description: String) -> Self {
+        let day = date.num_days_from_ce();
+        let mode = match day.rem_euclid(4) {
+            0 => Self::swift(),
+            1 => Self::cheese(
+                NonZeroUsize::new(Game::WIDTH - 1).unwrap(),
+                NonZeroU32::new(20),
+                (ExtDuration::Infinite, ExtDuration::Infinite),
+            ),
+            2 => Self::combo(game_modifiers::Combo::LAYOUTS[0], NonZeroU32::new(30)),
+            _ => Self::puzzle(),
+        };
+        // Spread out the seeds of consecutive days.
+        let seed = u64::from(day.unsigned_abs()).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        Self {
+            title: format!("{} {}", Self::TITLE_DAILY, mode.title),
+            description,
+            stat_and_order_desc: mode.stat_and_order_desc,
+            build: Box::new(move |builder: &GameBuilder| {
+                let mut builder = builder.clone();
+                builder.seed(seed);
+                (mode.build)(&builder)
+            }),
+        }
+    }
+
     pub const TITLE_ASCENT: &str = "Ascent";
     pub fn ascent() -> Self {
         Self {
@@ -0,0 +1,186 @@
+use std::{fs, io, path::Path};
+
+use falling_tetromino_engine::{
+    Configuration, DelayParameters, Game, GameBuilder, GameLimits, Stat,
+};
+
+use super::{game_modifiers, GameMode};
+
+/// A game mode defined in an external `.json` file, so modes can be added without recompiling.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ModeFile {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub stat_and_order_desc: (Stat, bool),
+    #[serde(default)]
+    pub fall_delay_params: Option<DelayParameters>,
+    #[serde(default)]
+    pub lock_delay_params: Option<DelayParameters>,
+    #[serde(default)]
+    pub limits: GameLimits,
+    /// Mods to build the game with, given by mod id and its arguments as JSON value.
+    /// The `Script` mod may instead be given the name of a script file next to the mode file.
+    #[serde(default)]
+    pub mod_ids_args: Vec<(String, serde_json::Value)>,
+}
+
+impl ModeFile {
+    /// The mods in the format of [`game_modifiers::reconstruct_build_modded`].
+    fn mod_ids_args(&self) -> Vec<(String, String)> {
+        self.mod_ids_args
+            .iter()
+            .map(|(mod_id, mod_args)| (mod_id.clone(), mod_args.to_string()))
+            .collect()
+    }
+
+    /// Apply the delays and limits given by the file, which the mods are then built on top of.
+    fn apply_settings(&self, builder: &mut GameBuilder) {
+        if let Some(fall_delay_params) = self.fall_delay_params {
+            builder.fall_delay_params(fall_delay_params);
+        }
+        if let Some(lock_delay_params) = self.lock_delay_params {
+            builder.lock_delay_params(lock_delay_params);
+        }
+        builder.game_limits(self.limits);
+    }
+
+    /// The first setting given by the file which differs in the built game's configuration.
+    fn overridden_setting(&self, config: &Configuration) -> Option<&'static str> {
+        if self
+            .fall_delay_params
+            .is_some_and(|params| params != config.fall_delay_params)
+        {
+            Some("fall_delay_params")
+        } else if self
+            .lock_delay_params
+            .is_some_and(|params| params != config.lock_delay_params)
+        {
+            Some("lock_delay_params")
+        } else if self.limits != GameLimits::default() && self.limits != config.game_limits {
+            Some("limits")
+        } else {
+            None
+        }
+    }
+
+    /// Replace script file names by the script, so it is stored with the game for replays.
+    fn load_script_files(&mut self, dir: &Path) -> Result<(), String> {
+        for (mod_id, mod_args) in &mut self.mod_ids_args {
+            if mod_id != game_modifiers::Script::MOD_ID {
+                continue;
+            }
+            if let serde_json::Value::String(file_name) = mod_args {
+                let source = fs::read_to_string(dir.join(&*file_name))
+                    .map_err(|e| format!("script {file_name:?}: {e}"))?;
+                *mod_args = serde_json::json!([file_name, source]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the mode can be built, so errors show up when loading instead of in-game.
+    fn check(&self) -> Result<(), String> {
+        if self.title.is_empty() {
+            return Err("empty title".to_owned());
+        }
+
+        let mut builder = Game::builder();
+        self.apply_settings(&mut builder);
+
+        let (_game, unrecognized_mod_ids) =
+            game_modifiers::reconstruct_build_modded(&builder, &self.mod_ids_args())?;
+
+        if !unrecognized_mod_ids.is_empty() {
+            return Err(format!("unrecognized mods: {unrecognized_mod_ids:?}"));
+        }
+
+        // Mods which set their own delays or limits would silently override those of the file.
+        for (mod_id, mod_args) in self.mod_ids_args() {
+            let (game, _unrecognized_mod_ids) = game_modifiers::reconstruct_build_modded(
+                &builder,
+                &vec![(mod_id.clone(), mod_args)],
+            )?;
+
+            if let Some(setting) = self.overridden_setting(&game.config) {
+                return Err(format!(
+                    "setting {setting:?} is overridden by mod {mod_id:?}"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn into_game_mode(mut self) -> GameMode {
+        let title = std::mem::take(&mut self.title);
+        let description = std::mem::take(&mut self.description);
+        let stat_and_order_desc = self.stat_and_order_desc;
+        let mod_ids_args = self.mod_ids_args();
+
+        GameMode {
+            title,
+            description,
+            stat_and_order_desc,
+            build: Box::new(move |builder: &GameBuilder| {
+                let mut builder = builder.clone();
+                self.apply_settings(&mut builder);
+
+                match game_modifiers::reconstruct_build_modded(&builder, &mod_ids_args) {
+                    Ok((game, _unrecognized_mod_ids)) => game,
+                    // Mode files are checked when loaded, but show the error should it still occur.
+                    Err(msg) => {
+                        let error_messages = vec![format!("ERROR: {msg}")];
+
+                        let print_error_msg_mod =
+                            game_modifiers::PrintMsgs::modifier(error_messages);
+
+                        builder.build_modded(vec![print_error_msg_mod])
+                    }
+                }
+            }),
+        }
+    }
+}
+
+/// Load all `.json` mode files from a directory, sorted by file name.
+///
+/// Files which could not be loaded are returned as error together with their file name.
+/// A missing directory just means there are no mode files.
+pub fn load_mode_files(dir: &Path) -> Vec<Result<ModeFile, (String, String)>> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => return vec![Err((dir.display().to_string(), e.to_string()))],
+    };
+
+    let mut paths = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let load = || -> Result<ModeFile, String> {
+                let contents = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+                let mut mode_file: ModeFile =
+                    serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+                mode_file.load_script_files(dir)?;
+                mode_file.check()?;
+                Ok(mode_file)
+            };
+
+            load().map_err(|e| {
+                let file_name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                (file_name, e)
+            })
+        })
+        .collect()
+}
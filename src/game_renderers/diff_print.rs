@@ -233,6 +233,14 @@ struct MinoParticle {
     tile_id: TileID,
 }
 
+/// Split times of the live game, to be compared to those of a best game.
+#[derive(PartialEq, PartialOrd, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+struct PaceSplits {
+    every_n_lines: u32,
+    best: Vec<InGameTime>,
+    current: Vec<InGameTime>,
+}
+
 #[derive(PartialEq, PartialOrd, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct DiffPrintRenderer {
     screen: TerminalScreenBuffer,
@@ -240,6 +248,44 @@ pub struct DiffPrintRenderer {
     buffered_text_msgs: Vec<(InGameTime, String)>,
     hard_drop_tiles: Vec<(HardDropTile, bool)>,
     mino_particles: Vec<(MinoParticle, bool)>,
+    pace_splits: Option<Box<PaceSplits>>,
+    #[serde(default)]
+    edit_cursor: Option<(usize, usize)>,
+    #[serde(skip)]
+    ghost_tiles: Vec<(Coord, TileID)>,
+}
+
+impl DiffPrintRenderer {
+    pub fn set_pace_splits(&mut self, every_n_lines: u32, best: Vec<InGameTime>) {
+        self.pace_splits = Some(Box::new(PaceSplits {
+            every_n_lines,
+            best,
+            current: Vec::new(),
+        }));
+    }
+
+    pub fn clear_pace_splits(&mut self) {
+        self.pace_splits = None;
+    }
+
+    pub fn has_pace_splits(&self) -> bool {
+        self.pace_splits.is_some()
+    }
+
+    pub fn set_current_splits(&mut self, current: &[InGameTime]) {
+        if let Some(pace_splits) = &mut self.pace_splits {
+            pace_splits.current.clear();
+            pace_splits.current.extend_from_slice(current);
+        }
+    }
+
+    pub fn set_edit_cursor(&mut self, edit_cursor: Option<(usize, usize)>) {
+        self.edit_cursor = edit_cursor;
+    }
+
+    pub fn set_ghost_tiles(&mut self, ghost_tiles: Vec<(Coord, TileID)>) {
+        self.ghost_tiles = ghost_tiles;
+    }
 }
 
 impl Renderer for DiffPrintRenderer {
@@ -247,6 +293,15 @@ impl Renderer for DiffPrintRenderer {
         &mut self,
         feed: impl IntoIterator<Item = (Notification, InGameTime)>,
     ) {
+        // Ghost tiles are only shown until the next piece locks.
+        let feed = feed.into_iter().collect::<Vec<_>>();
+        if feed
+            .iter()
+            .any(|(notif, _)| matches!(notif, Notification::PieceLocked { .. }))
+        {
+            self.ghost_tiles.clear();
+        }
+
         // Update stored events.
         self.notification_feed_buffer
             .extend(feed.into_iter().map(|(notif, time)| (notif, time, true)));
@@ -254,6 +309,7 @@ impl Renderer for DiffPrintRenderer {
 
     fn reset_game_associated_state(&mut self) {
         self.notification_feed_buffer.clear();
+        self.ghost_tiles.clear();
         self.buffered_text_msgs.clear();
         self.hard_drop_tiles.clear();
         self.mino_particles.clear();
@@ -284,31 +340,41 @@ impl Renderer for DiffPrintRenderer {
         let gravity = game.state().fall_delay.as_hertz();
         // Screen: some titles.
         let modename_len = meta_data.title.len().max(14);
-        // FIXME: Only displaying the first (of up to four) limits found.
-        let (endcond_title, endcond_value) = if let Some((c, _)) = game
+        // Remaining amounts until each limit is reached, limits to win first.
+        // Limits to lose are marked with '!' instead of ':'.
+        let mut limits_left = game
             .config
             .game_limits
             .iter()
-            .find(|(_stat, to_win)| *to_win)
-        {
-            match c {
-                Stat::TimeElapsed(t) => (
-                    "Time left:",
-                    fmt_duration(t.saturating_sub(game.state().time)),
-                ),
-                Stat::PiecesLocked(p) => ("Pieces left:", p.saturating_sub(pieces).to_string()),
-                Stat::LinesCleared(l) => (
-                    "Lines left:",
-                    l.saturating_sub(game.state().lineclears).to_string(),
-                ),
-                Stat::PointsScored(s) => (
-                    "Points left:",
-                    s.saturating_sub(game.state().points).to_string(),
-                ),
-            }
-        } else {
-            ("", "".to_owned())
-        };
+            .map(|(c, to_win)| {
+                let (title, value) = match c {
+                    Stat::TimeElapsed(t) => (
+                        "Time left",
+                        fmt_duration(t.saturating_sub(game.state().time)),
+                    ),
+                    Stat::PiecesLocked(p) => ("Pieces left", p.saturating_sub(pieces).to_string()),
+                    Stat::LinesCleared(l) => (
+                        "Lines left",
+                        l.saturating_sub(game.state().lineclears).to_string(),
+                    ),
+                    Stat::PointsScored(s) => (
+                        "Points left",
+                        s.saturating_sub(game.state().points).to_string(),
+                    ),
+                };
+                (
+                    format!("{title}{}", if to_win { ':' } else { '!' }),
+                    value,
+                    to_win,
+                )
+            })
+            .collect::<Vec<_>>();
+        limits_left.sort_by_key(|(_, _, to_win)| !to_win);
+        // The first limit goes into the side panel, the others are listed below the stats.
+        let (endcond_title, endcond_value) = limits_left
+            .first()
+            .map(|(title, value, _)| (title.clone(), value.clone()))
+            .unwrap_or_default();
 
         let show_hold = game.state().piece_held.is_some();
         let show_next = !game.state().piece_preview.is_empty();
@@ -414,6 +480,8 @@ impl Renderer for DiffPrintRenderer {
         let (x_rep_hdr, y_rep_hdr) = (1, 1);
         let (x_rep_spd, y_rep_spd) = (1, 11);
         let (x_rep_len, y_rep_len) = (1, 12);
+        let (x_limits, y_limits) = (1, if replay_extra.is_some() { 13 } else { 11 });
+        let (x_pace, y_pace) = (1, 12.max(y_limits + limits_left.len().saturating_sub(1)));
         let (x_buttonst, y_buttonst) = (48, 17);
         // FIXME: Returning `None` as soon as it is OOB for the rectangle of our custom game screen buffer.
         // But this is wasteful if there's actual space in the TUI above and we cut off 'for no reason'.
@@ -471,6 +539,53 @@ impl Renderer for DiffPrintRenderer {
             );
         }
 
+        // Further limits.
+        for (dy, (title, value, _)) in limits_left.iter().skip(1).enumerate() {
+            self.screen
+                .buffer_str(&format!("{title} {value}"), None, (x_limits, y_limits + dy));
+        }
+
+        if let (Some(pace_splits), None) = (&self.pace_splits, replay_extra) {
+            // Live game with a best game to compare to, show pace.
+            // Difference at the latest split reached.
+            let n_splits = pace_splits.current.len();
+            if let (Some(current), Some(best)) = (
+                pace_splits.current.last(),
+                n_splits
+                    .checked_sub(1)
+                    .and_then(|i| pace_splits.best.get(i)),
+            ) {
+                let (sign, delta, color) = if current <= best {
+                    ('-', *best - *current, Color::Green)
+                } else {
+                    ('+', *current - *best, Color::Red)
+                };
+                self.screen.buffer_str(
+                    &format!(
+                        "{}L split: {sign}{:.2}s",
+                        n_splits * usize::try_from(pace_splits.every_n_lines).unwrap(),
+                        delta.as_secs_f64()
+                    ),
+                    Some(color),
+                    (x_pace, y_pace),
+                );
+            }
+
+            // Best time for the next split.
+            if let Some(next_best) = pace_splits.best.get(pace_splits.current.len()) {
+                self.screen.buffer_str(
+                    &format!(
+                        "Best {}L: {}",
+                        (pace_splits.current.len() + 1)
+                            * usize::try_from(pace_splits.every_n_lines).unwrap(),
+                        fmt_duration(*next_best)
+                    ),
+                    None,
+                    (x_pace, y_pace + 1),
+                );
+            }
+        }
+
         // Draw button state.
         if settings.graphics().show_button_state || replay_extra.is_some() {
             let n253 = NonZeroU8::try_from(253).unwrap();
@@ -519,11 +634,11 @@ impl Renderer for DiffPrintRenderer {
             }
         }
 
-        let (tile_ground, tile_shadow, tile_active, tile_preview) =
+        let (tile_ground, tile_shadow, tile_active, tile_preview, tile_ghost) =
             match settings.graphics().glyphset {
-                Glyphset::Elektronika_60 => ("▮▮", " .", "▮▮", "▮▮"),
-                Glyphset::ASCII => ("##" /*"$$"*/, "::", "[]", "[]"),
-                Glyphset::Unicode => ("██", "░░", "▓▓", "██" /*"▒▒"*/),
+                Glyphset::Elektronika_60 => ("▮▮", " .", "▮▮", "▮▮", "[]"),
+                Glyphset::ASCII => ("##" /*"$$"*/, "::", "[]", "[]", "()"),
+                Glyphset::Unicode => ("██", "░░", "▓▓", "██" /*"▒▒"*/, "▒▒"),
             };
 
         // Draw preview.
@@ -734,6 +849,21 @@ impl Renderer for DiffPrintRenderer {
             }
         }
 
+        // Board: draw ghost tiles.
+        for (tile_pos, tile_id) in &self.ghost_tiles {
+            if let Some(xy) = pos_board(*tile_pos) {
+                self.screen.buffer_str(tile_ghost, get_color(*tile_id), xy);
+            }
+        }
+
+        // Board: draw cursor of board editing.
+        if let Some((x, y)) = self.edit_cursor {
+            if let Some(xy) = pos_board((isize::try_from(x).unwrap(), isize::try_from(y).unwrap()))
+            {
+                self.screen.buffer_str("<>", Some(Color::Yellow), xy);
+            }
+        }
+
         let (w_term, h_term) = terminal::size()?; // FIXME: Hack.
         for (
             MinoParticle {
@@ -9,7 +9,7 @@ mod prototype;
 
 use std::io::{self, Write};
 
-use falling_tetromino_engine::{Game, InGameTime, Notification};
+use falling_tetromino_engine::{Coord, Game, InGameTime, Notification, TileID};
 
 use crate::{
     application::{GameMetaData, Settings, TemporaryAppData},
@@ -74,6 +74,47 @@ impl TetroTUIRenderer {
         }
     }
 
+    /// Set the split times of a best game to show the live game's pace against (if supported by the renderer).
+    pub fn set_pace_splits(&mut self, every_n_lines: u32, best: Vec<InGameTime>) {
+        if let Self::DiffPrint(r) = self {
+            r.set_pace_splits(every_n_lines, best);
+        }
+    }
+
+    pub fn clear_pace_splits(&mut self) {
+        if let Self::DiffPrint(r) = self {
+            r.clear_pace_splits();
+        }
+    }
+
+    pub fn has_pace_splits(&self) -> bool {
+        match self {
+            Self::DiffPrint(r) => r.has_pace_splits(),
+            _ => false,
+        }
+    }
+
+    /// Set the split times the live game has taken so far (if supported by the renderer).
+    pub fn set_current_splits(&mut self, current: &[InGameTime]) {
+        if let Self::DiffPrint(r) = self {
+            r.set_current_splits(current);
+        }
+    }
+
+    /// Set the board cell to highlight as being edited (if supported by the renderer).
+    pub fn set_edit_cursor(&mut self, edit_cursor: Option<(usize, usize)>) {
+        if let Self::DiffPrint(r) = self {
+            r.set_edit_cursor(edit_cursor);
+        }
+    }
+
+    /// Set tiles to show as placement suggestions until the next piece locks (if supported by the renderer).
+    pub fn set_ghost_tiles(&mut self, ghost_tiles: Vec<(Coord, TileID)>) {
+        if let Self::DiffPrint(r) = self {
+            r.set_ghost_tiles(ghost_tiles);
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             Self::DiffPrint(_) => "Default",
@@ -1,4 +1,5 @@
 mod application;
+mod bot_interface;
 mod fmt_helpers;
 mod game_modes;
 mod game_renderers;
@@ -7,8 +8,9 @@ mod graphics_settings;
 mod keybinds;
 mod live_input_handler;
 mod palette;
+mod solver;
 
-use std::{io, path::PathBuf};
+use std::{fs, io, path::PathBuf};
 
 use clap::Parser;
 
@@ -47,6 +49,12 @@ fn savefile_path() -> PathBuf {
         .join(savefile_name())
 }
 
+fn modes_dir_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tetro-tui_modes")
+}
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
@@ -62,21 +70,92 @@ struct Args {
     /// Example: |█▀ ▄██▀ ▀█| => `tetro-tui --board="O  OOO   OXX  XXX XX"` or `tetro-tui -b "O  OOO   OXX  XXX XX"`.
     #[arg(short, long)]
     board: Option<String>,
+    /// Custom starting board or puzzle when playing a custom game, given as fumen string (https://fumen.zui.jp/).
+    /// A single page is used as starting board, several pages are played as puzzle stages.
+    /// Example: `tetro-tui --fumen="v115@9gI8AeI8AeI8AeI8KeAgH"`.
+    #[arg(long)]
+    fumen: Option<String>,
+    /// Fixed sequence of pieces to be dealt when playing a custom game, given as tetromino letters.
+    /// The sequence repeats if it ends in `*`, otherwise pieces are generated as usual once it is used up.
+    /// Example: `tetro-tui --queue="IOLJSZT*"`.
+    #[arg(long)]
+    queue: Option<String>,
+    /// Command to start an external bot which plays the game instead of the keyboard.
+    /// The bot is spoken to over its stdin/stdout, one JSON message per line, in a protocol
+    /// modeled after the community Tetris Bot Protocol (start, new_piece, suggest, play).
+    /// Arguments are split as in a shell, so they can be quoted if they contain spaces.
+    /// Example: `tetro-tui --bot-cmd="python3 my_bot.py"`.
+    #[arg(long)]
+    bot_cmd: Option<String>,
+    /// Check a replay file exported from the Daily Challenge results by re-simulating it, then exit.
+    /// Example: `tetro-tui --verify-replay=tetro-tui_replay_Daily-Swift_2026-10-18-12-00.json`.
+    #[arg(long)]
+    verify_replay: Option<PathBuf>,
+    /// Open a replay in the human-readable text format (as exported with [Ctrl+X] from 'Scores and Replays')
+    /// in the TAS editor, where it can be watched and saved.
+    /// Example: `tetro-tui --import-replay=tetro-tui_replay_Puzzle_2026-10-18-12-00.replay.txt`.
+    #[arg(long)]
+    import_replay: Option<PathBuf>,
+    /// Check whether the stages of the built-in and loaded puzzles (fumen, stored custom modes, mode files)
+    /// can be solved with the pieces given and the rotation system of the gameplay settings, then exit.
+    /// Example: `tetro-tui --check-puzzles`.
+    #[arg(long)]
+    check_puzzles: bool,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Read commandline arguments.
     let args = Args::parse();
 
+    // Only verify a replay without starting the application.
+    if let Some(replay_path) = args.verify_replay {
+        println!("{}", application::verify_replay_file(&replay_path)?);
+        return Ok(());
+    }
+
+    // Check a given fumen before starting the application.
+    let fumen_import = args
+        .fumen
+        .map(|fumen| application::NewGameSettings::decode_fumen(&fumen))
+        .transpose()?;
+
+    // Check a given text replay before starting the application.
+    let replay_import = match args.import_replay {
+        Some(replay_path) => Some(application::decode_replay_text(&fs::read_to_string(
+            replay_path,
+        )?)?),
+        None => None,
+    };
+
+    // Check a given piece sequence before starting the application.
+    if let Some(queue) = &args.queue {
+        game_modes::game_modifiers::PieceSequence::parse(queue)?;
+    }
+
     // Initialize application.
     let stdout = io::BufWriter::new(io::stdout());
     let mut app = application::Application::with_savefile_and_cmdlineoptions(
         stdout,
         savefile_path(),
+        modes_dir_path(),
         args.seed,
         args.board,
+        fumen_import,
+        args.queue,
+        args.bot_cmd,
+        replay_import,
     );
 
+    // Only check puzzles without running the application.
+    if args.check_puzzles {
+        let (report, all_solvable) = app.check_puzzles();
+        println!("{report}");
+        if !all_solvable {
+            return Err("some puzzle stages are not solvable".into());
+        }
+        return Ok(());
+    }
+
     // Catch panics and write error to separate file, so it isn't lost due to app's terminal shenanigans.
     std::panic::set_hook(Box::new(|panic_info| {
         #[cfg(debug_assertions)]
@@ -133,6 +133,60 @@ impl<'de> serde_with::DeserializeAs<'de, Color> for ColorDummyType {
     }
 }
 
+/// Approximate RGB values of a terminal color, based on common xterm defaults.
+pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    use Color as C;
+    const ANSI_16: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    let rgb = match color {
+        C::Reset => return None,
+        C::Rgb { r, g, b } => (r, g, b),
+        C::AnsiValue(n @ 0..=15) => ANSI_16[usize::from(n)],
+        C::AnsiValue(n @ 16..=231) => {
+            let level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+            let n = n - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        C::AnsiValue(n) => {
+            let grey = 8 + 10 * (n - 232);
+            (grey, grey, grey)
+        }
+        C::Black => ANSI_16[0],
+        C::DarkRed => ANSI_16[1],
+        C::DarkGreen => ANSI_16[2],
+        C::DarkYellow => ANSI_16[3],
+        C::DarkBlue => ANSI_16[4],
+        C::DarkMagenta => ANSI_16[5],
+        C::DarkCyan => ANSI_16[6],
+        C::Grey => ANSI_16[7],
+        C::DarkGrey => ANSI_16[8],
+        C::Red => ANSI_16[9],
+        C::Green => ANSI_16[10],
+        C::Yellow => ANSI_16[11],
+        C::Blue => ANSI_16[12],
+        C::Magenta => ANSI_16[13],
+        C::Cyan => ANSI_16[14],
+        C::White => ANSI_16[15],
+    };
+    Some(rgb)
+}
+
 impl Palette {
     pub fn get(&self, x: &TileID) -> Option<&Color> {
         self.tile_to_col.get(&x.get())
@@ -0,0 +1,507 @@
+use std::collections::{HashSet, VecDeque};
+
+use falling_tetromino_engine::{
+    Board, Coord, Game, Line, Piece, RotationSystem, Tetromino, TileID,
+};
+
+/// Find all places a piece can be locked at from its current position, moving it and rotating it (also by 180°) with the given rotation system.
+///
+/// Places which look the same (e.g. an `O` in different orientations) are only listed once.
+pub fn placements(start: Piece, board: &Board, rotation_system: &RotationSystem) -> Vec<Piece> {
+    if !start.fits_onto(board) {
+        return Vec::new();
+    }
+
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut placements = Vec::new();
+    let mut placement_tiles = HashSet::new();
+
+    while let Some(piece) = queue.pop_front() {
+        // A piece locked entirely above the skyline would end the game.
+        let is_below_skyline = piece
+            .tiles()
+            .iter()
+            .any(|((_x, y), _)| *y < Game::LOCK_OUT_HEIGHT as isize);
+        if !piece.is_airborne(board) && is_below_skyline && placement_tiles.insert(tiles(&piece)) {
+            placements.push(piece);
+        }
+
+        let successors = [
+            piece.offset_on(board, (-1, 0)).ok(),
+            piece.offset_on(board, (1, 0)).ok(),
+            piece.offset_on(board, (0, -1)).ok(),
+            rotation_system.rotate(&piece, board, -1),
+            rotation_system.rotate(&piece, board, 1),
+            rotation_system.rotate(&piece, board, 2),
+        ];
+
+        for successor in successors.into_iter().flatten() {
+            if visited.insert(successor) {
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    placements
+}
+
+/// Put a piece onto the board and clear the lines it completes.
+pub fn lock(board: &Board, piece: &Piece) -> Board {
+    let mut board = *board;
+    for ((x, y), tile_id) in piece.tiles() {
+        board[y as usize][x as usize] = Some(tile_id);
+    }
+
+    let mut lines = board
+        .into_iter()
+        .filter(|line| line.iter().any(Option::is_none))
+        .collect::<Vec<_>>();
+    lines.resize(Game::HEIGHT, Line::default());
+
+    lines.try_into().unwrap()
+}
+
+/// Search for placements of the given pieces, in order, that clear the board completely (as required by `Puzzle` stages).
+///
+/// The pieces are given in the position they start from, e.g. as spawned or as currently in play.
+pub fn solve_puzzle(
+    board: &Board,
+    pieces: &[Piece],
+    rotation_system: &RotationSystem,
+) -> Option<Vec<Piece>> {
+    let mut solution = Vec::new();
+    let mut failed = HashSet::new();
+
+    search_clear(board, pieces, rotation_system, &mut failed, &mut solution).then_some(solution)
+}
+
+fn search_clear(
+    board: &Board,
+    pieces: &[Piece],
+    rotation_system: &RotationSystem,
+    failed: &mut HashSet<(Board, usize)>,
+    solution: &mut Vec<Piece>,
+) -> bool {
+    let Some((piece, remaining_pieces)) = pieces.split_first() else {
+        return board.iter().all(|line| *line == Line::default());
+    };
+
+    // Every line with tiles in it still needs to be completed, for which there have to be enough pieces left.
+    let cells_to_fill = board
+        .iter()
+        .filter(|line| line.iter().any(Option::is_some))
+        .map(|line| line.iter().filter(|tile| tile.is_none()).count())
+        .sum::<usize>();
+    if cells_to_fill > 4 * pieces.len() || failed.contains(&(*board, pieces.len())) {
+        return false;
+    }
+
+    for placement in placements(*piece, board, rotation_system) {
+        solution.push(placement);
+        let next_board = lock(board, &placement);
+        if search_clear(
+            &next_board,
+            remaining_pieces,
+            rotation_system,
+            failed,
+            solution,
+        ) {
+            return true;
+        }
+        solution.pop();
+    }
+
+    failed.insert((*board, pieces.len()));
+
+    false
+}
+
+/// How many positions without a perfect clear to go through before giving up, so long previews do not stall the search.
+pub const PERFECT_CLEAR_SEARCH_LIMIT: usize = 10_000;
+
+/// Search for placements of the piece in play, the held piece and the preview that clear the board of the game completely.
+///
+/// Holding is allowed as in game, i.e. once per piece unless the piece in play was just swapped in.
+/// Lower perfect clears are searched first, and the search gives up after [`PERFECT_CLEAR_SEARCH_LIMIT`] dead ends.
+pub fn find_perfect_clear(game: &Game) -> Option<Vec<Piece>> {
+    let mut piece_preview = game
+        .state()
+        .piece_preview
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    // In between pieces, start from the one about to spawn.
+    let piece = match game.phase().piece() {
+        Some(piece) => *piece,
+        None if !piece_preview.is_empty() => piece_preview.remove(0).piece_spawn_state(),
+        None => return None,
+    };
+    let board = &game.state().board;
+    let piece_held = game.state().piece_held;
+    let rotation_system = &game.config.rotation_system;
+
+    let tiles_filled = board.iter().flatten().filter(|tile| tile.is_some()).count();
+    let height_filled = board
+        .iter()
+        .rposition(|line| line.iter().any(Option::is_some))
+        .map_or(0, |y| y + 1);
+    // One piece always remains held once hold is used, so at most this many pieces can be placed.
+    let tiles_available = 4 * (1 + piece_preview.len());
+
+    let mut failed = HashSet::new();
+
+    // Try clearing exactly the lines up to each height, as long as the pieces can fill them.
+    for height in height_filled.max(1)..=Game::LOCK_OUT_HEIGHT {
+        let Some(tiles_to_fill) = (Line::default().len() * height).checked_sub(tiles_filled) else {
+            continue;
+        };
+        if tiles_available < tiles_to_fill {
+            break;
+        }
+        if tiles_to_fill % 4 != 0 {
+            continue;
+        }
+
+        let mut solution = Vec::new();
+        if search_perfect_clear(
+            board,
+            height,
+            piece,
+            piece_held.map(|(tet, _)| tet),
+            piece_held.is_none_or(|(_, swap_allowed)| swap_allowed),
+            &piece_preview,
+            rotation_system,
+            &mut failed,
+            &mut solution,
+        ) {
+            return Some(solution);
+        }
+    }
+
+    None
+}
+
+/// Board, height to clear, piece in play, held piece, whether holding is allowed and number of preview pieces left.
+type PerfectClearPosition = (Board, usize, Piece, Option<Tetromino>, bool, usize);
+
+#[allow(clippy::too_many_arguments)]
+fn search_perfect_clear(
+    board: &Board,
+    height: usize,
+    piece: Piece,
+    held: Option<Tetromino>,
+    hold_allowed: bool,
+    preview: &[Tetromino],
+    rotation_system: &RotationSystem,
+    failed: &mut HashSet<PerfectClearPosition>,
+    solution: &mut Vec<Piece>,
+) -> bool {
+    if PERFECT_CLEAR_SEARCH_LIMIT <= failed.len() {
+        return false;
+    }
+
+    // All lines below the height still need to be completed, for which there have to be enough pieces left.
+    let tiles_to_fill = board[..height]
+        .iter()
+        .flatten()
+        .filter(|tile| tile.is_none())
+        .count();
+    let key = (*board, height, piece, held, hold_allowed, preview.len());
+    if tiles_to_fill > 4 * (1 + preview.len())
+        || !are_regions_fillable(board, height)
+        || failed.contains(&key)
+    {
+        return false;
+    }
+
+    // Nothing is above the lines to be cleared, so the piece can skip falling through the empty space.
+    let lowest_y = piece.tiles().iter().map(|((_x, y), _)| *y).min().unwrap();
+    let fall = lowest_y - (height as isize + 4);
+    let piece = if 0 < fall {
+        piece.offset_on(board, (0, -fall)).unwrap_or(piece)
+    } else {
+        piece
+    };
+
+    // Place the piece in play, without building above the lines to be cleared.
+    for placement in placements(piece, board, rotation_system) {
+        if placement
+            .tiles()
+            .iter()
+            .any(|((_x, y), _)| height <= *y as usize)
+        {
+            continue;
+        }
+
+        solution.push(placement);
+        let next_board = lock(board, &placement);
+        if next_board.iter().all(|line| *line == Line::default()) {
+            return true;
+        }
+        let lines_cleared = (0..height)
+            .filter(|y| {
+                let tiles_empty = board[*y].iter().filter(|tile| tile.is_none()).count();
+                let tiles_placed = placement
+                    .tiles()
+                    .iter()
+                    .filter(|((_x, y_tile), _)| *y_tile as usize == *y)
+                    .count();
+                tiles_empty == tiles_placed
+            })
+            .count();
+        if let Some((next_tet, next_preview)) = preview.split_first() {
+            if search_perfect_clear(
+                &next_board,
+                height - lines_cleared,
+                next_tet.piece_spawn_state(),
+                held,
+                true,
+                next_preview,
+                rotation_system,
+                failed,
+                solution,
+            ) {
+                return true;
+            }
+        }
+        solution.pop();
+    }
+
+    // Hold the piece in play instead.
+    if hold_allowed {
+        let swapped = match held {
+            Some(held_tet) => Some((held_tet, preview)),
+            None => preview
+                .split_first()
+                .map(|(next_tet, next_preview)| (*next_tet, next_preview)),
+        };
+        if let Some((next_tet, next_preview)) = swapped {
+            if search_perfect_clear(
+                board,
+                height,
+                next_tet.piece_spawn_state(),
+                Some(piece.tetromino),
+                false,
+                next_preview,
+                rotation_system,
+                failed,
+                solution,
+            ) {
+                return true;
+            }
+        }
+    }
+
+    failed.insert(key);
+
+    false
+}
+
+/// Whether each separate empty region below the height could be filled by whole pieces, i.e. has a multiple of four cells.
+fn are_regions_fillable(board: &Board, height: usize) -> bool {
+    let mut visited = [[false; Game::WIDTH]; Game::HEIGHT];
+
+    for y in 0..height {
+        for x in 0..Game::WIDTH {
+            if visited[y][x] || board[y][x].is_some() {
+                continue;
+            }
+
+            // Flood fill the region.
+            visited[y][x] = true;
+            let mut stack = vec![(x, y)];
+            let mut region_size = 0;
+            while let Some((x, y)) = stack.pop() {
+                region_size += 1;
+                let neighbors = [
+                    x.checked_sub(1).map(|x| (x, y)),
+                    (x + 1 < Game::WIDTH).then_some((x + 1, y)),
+                    y.checked_sub(1).map(|y| (x, y)),
+                    (y + 1 < height).then_some((x, y + 1)),
+                ];
+                for (x, y) in neighbors.into_iter().flatten() {
+                    if !visited[y][x] && board[y][x].is_none() {
+                        visited[y][x] = true;
+                        stack.push((x, y));
+                    }
+                }
+            }
+
+            if region_size % 4 != 0 {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// The tiles of the given placements as they fill the board, i.e. without the shift from lines cleared in between.
+pub fn placement_tiles(board: &Board, placements: &[Piece]) -> Vec<(Coord, TileID)> {
+    let mut board = *board;
+    // The original row of each row of the board after lines have been cleared.
+    let mut rows = (0..Game::HEIGHT).collect::<Vec<_>>();
+    let mut next_row = Game::HEIGHT;
+    let mut tiles = Vec::new();
+
+    for placement in placements {
+        for ((x, y), tile_id) in placement.tiles() {
+            tiles.push(((x, rows[y as usize] as isize), tile_id));
+        }
+
+        let next_board = lock(&board, placement);
+        let mut filled_board = board;
+        for ((x, y), tile_id) in placement.tiles() {
+            filled_board[y as usize][x as usize] = Some(tile_id);
+        }
+        for y in (0..Game::HEIGHT).rev() {
+            if filled_board[y].iter().all(Option::is_some) {
+                rows.remove(y);
+                // Rows coming in from the top are out of sight anyway.
+                rows.push(next_row);
+                next_row += 1;
+            }
+        }
+        board = next_board;
+    }
+
+    tiles
+}
+
+fn tiles(piece: &Piece) -> [Coord; 4] {
+    let mut coords = piece.tiles().map(|(coord, _)| coord);
+    coords.sort();
+    coords
+}
+
+#[cfg(test)]
+mod tests {
+    use falling_tetromino_engine::InGameTime;
+
+    use super::*;
+    use crate::game_modes::game_modifiers::{self, StartBoard};
+
+    /// Lines of `'X'` = filled and `' '` = empty, from the bottom.
+    fn decode_board(lines: &[&str]) -> Board {
+        StartBoard::decode_board(&lines.concat())
+    }
+
+    fn game(lines: &[&str], piece_sequence: &str) -> Game {
+        let mod_ids_args = vec![
+            (
+                StartBoard::MOD_ID.to_owned(),
+                serde_json::to_string(&lines.concat()).unwrap(),
+            ),
+            (
+                game_modifiers::PieceSequence::MOD_ID.to_owned(),
+                serde_json::to_string(piece_sequence).unwrap(),
+            ),
+        ];
+        let (mut game, _unrecognized_mod_ids) =
+            game_modifiers::reconstruct_build_modded(&Game::builder(), &mod_ids_args).unwrap();
+        // Fill the preview.
+        game.update(InGameTime::ZERO, None).unwrap();
+        game
+    }
+
+    #[test]
+    fn placements_on_empty_board() {
+        let rotation_system = RotationSystem::default();
+        let count = |tet: Tetromino| {
+            placements(tet.piece_spawn_state(), &Board::default(), &rotation_system).len()
+        };
+
+        assert_eq!(count(Tetromino::O), 9);
+        assert_eq!(count(Tetromino::I), 7 + 10);
+        assert_eq!(count(Tetromino::T), 8 + 9 + 8 + 9);
+    }
+
+    #[test]
+    fn placements_include_180_rotations() {
+        // The J only fits under the overhang on the left by turning around from its spawn orientation.
+        let board = decode_board(&["  X  X  X ", "X    X    "]);
+
+        let placements = placements(
+            Tetromino::J.piece_spawn_state(),
+            &board,
+            &RotationSystem::Ocular,
+        );
+
+        assert!(placements
+            .iter()
+            .any(|piece| tiles(piece) == [(0, 0), (1, 0), (1, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn lock_clears_completed_lines() {
+        let board = decode_board(&["XXXXXXXXX ", "XXXXXXXXX ", "X         "]);
+        let piece = placements(
+            Tetromino::I.piece_spawn_state(),
+            &board,
+            &RotationSystem::Super,
+        )
+        .into_iter()
+        .find(|piece| tiles(piece) == [(9, 0), (9, 1), (9, 2), (9, 3)])
+        .unwrap();
+
+        let mut expected_board = decode_board(&["X         "]);
+        expected_board[0][9] = Some(Tetromino::I.tile_id());
+        expected_board[1][9] = Some(Tetromino::I.tile_id());
+
+        assert_eq!(lock(&board, &piece), expected_board);
+    }
+
+    #[test]
+    fn solves_puzzle_with_known_solution() {
+        let board = decode_board(&["XXXXXX    ", "XXXXXX    "]);
+        let pieces = [Tetromino::O, Tetromino::O].map(Tetromino::piece_spawn_state);
+
+        let solution = solve_puzzle(&board, &pieces, &RotationSystem::default()).unwrap();
+
+        let mut tiles_placed = solution.iter().flat_map(tiles).collect::<Vec<_>>();
+        tiles_placed.sort();
+        assert_eq!(
+            tiles_placed,
+            [
+                (6, 0),
+                (6, 1),
+                (7, 0),
+                (7, 1),
+                (8, 0),
+                (8, 1),
+                (9, 0),
+                (9, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_solve_puzzle_without_solution() {
+        let board = decode_board(&["XXXXXXXXX ", "XXXXXXXXX ", "XXXXXXXXX ", "XXXXXXXXX "]);
+        let pieces = [Tetromino::O.piece_spawn_state()];
+
+        assert_eq!(
+            solve_puzzle(&board, &pieces, &RotationSystem::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_perfect_clear_through_hold() {
+        let lines = ["XXXXXXXXX ", "XXXXXXXXX ", "XXXXXXXXX ", "XXXXXXXXX "];
+
+        let solution = find_perfect_clear(&game(&lines, "OI*")).unwrap();
+
+        let [piece] = solution.as_slice() else {
+            panic!("expected one piece, got {}", solution.len());
+        };
+        assert_eq!(piece.tetromino, Tetromino::I);
+        assert_eq!(tiles(piece), [(9, 0), (9, 1), (9, 2), (9, 3)]);
+    }
+
+    #[test]
+    fn finds_no_perfect_clear_for_odd_tile_count() {
+        assert_eq!(find_perfect_clear(&game(&["X"], "O*")), None);
+    }
+}